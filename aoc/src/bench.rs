@@ -0,0 +1,146 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use utils::Solution;
+
+/// Measured durations of every phase, one entry per run.
+#[derive(Debug, Default)]
+pub struct Samples {
+    parse: Vec<Duration>,
+    part1: Vec<Duration>,
+    part2: Vec<Duration>,
+}
+
+/// Times parsing and both parts separately. Every run parses the input again,
+/// so caches built during parsing are not shared between runs.
+pub fn time<S: Solution>(input: &str, runs: usize) -> Result<Samples> {
+    let mut samples = Samples::default();
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = black_box(S::parse(input)?);
+        samples.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed)?);
+        samples.part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&parsed)?);
+        samples.part2.push(start.elapsed());
+    }
+
+    Ok(samples)
+}
+
+#[derive(Debug, Serialize)]
+pub struct PhaseStats {
+    min_ns: u64,
+    median_ns: u64,
+    mean_ns: u64,
+    max_ns: u64,
+}
+
+impl PhaseStats {
+    fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos = samples
+            .iter()
+            .map(|sample| sample.as_nanos() as u64)
+            .collect::<Vec<_>>();
+        nanos.sort_unstable();
+
+        let runs = nanos.len().max(1) as u64;
+
+        Self {
+            min_ns: nanos.first().copied().unwrap_or_default(),
+            median_ns: nanos.get(nanos.len() / 2).copied().unwrap_or_default(),
+            mean_ns: nanos.iter().sum::<u64>() / runs,
+            max_ns: nanos.last().copied().unwrap_or_default(),
+        }
+    }
+
+    fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayReport {
+    day: u8,
+    parse: PhaseStats,
+    part1: PhaseStats,
+    part2: PhaseStats,
+}
+
+impl DayReport {
+    pub fn new(day: u8, samples: &Samples) -> Self {
+        Self {
+            day,
+            parse: PhaseStats::from_samples(&samples.parse),
+            part1: PhaseStats::from_samples(&samples.part1),
+            part2: PhaseStats::from_samples(&samples.part2),
+        }
+    }
+
+    fn total(&self) -> Duration {
+        self.parse.median() + self.part1.median() + self.part2.median()
+    }
+}
+
+/// Benchmark results of all measured days. Serialized form is stable (days in the order
+/// they were measured, fixed field order), so reports from two commits can be diffed directly.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    runs: usize,
+    days: Vec<DayReport>,
+}
+
+impl Report {
+    pub fn new(runs: usize, days: Vec<DayReport>) -> Self {
+        Self { runs, days }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Median of every phase, one day per row.
+    pub fn table(&self) -> String {
+        let mut table = format!(
+            "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}\n",
+            "day", "parse", "part 1", "part 2", "total"
+        );
+
+        for day in self.days.iter() {
+            table.push_str(&format!(
+                "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}\n",
+                day.day,
+                human(day.parse.median()),
+                human(day.part1.median()),
+                human(day.part2.median()),
+                human(day.total()),
+            ));
+        }
+
+        let total = self.days.iter().map(DayReport::total).sum();
+        table.push_str(&format!("{:>3}  {:>46}\n", "all", human(total)));
+        table.push_str(&format!("medians of {} runs\n", self.runs));
+
+        table
+    }
+}
+
+pub fn human(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
@@ -0,0 +1,268 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::days::{self, Part};
+
+/// Default location of the cache, relative to the workspace root like the inputs.
+pub const DIRECTORY: &str = ".aoc-cache";
+
+/// 64-bit FNV-1a. Cache keys have to stay the same between builds, which the standard
+/// library hasher does not promise.
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Identifies the answers of one version of a day's solver to one input.
+fn key(day: u8, version: u32, input: &str) -> u64 {
+    fnv1a(
+        [day]
+            .into_iter()
+            .chain(version.to_le_bytes())
+            .chain(input.bytes()),
+    )
+}
+
+/// Answers to one input, found by one version of the day's solver.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    day: u8,
+    version: u32,
+    answers: BTreeMap<u8, String>,
+}
+
+/// Cached answers of one day to one input. Parts which were not solved yet are missing.
+pub struct CachedAnswers {
+    path: PathBuf,
+    entry: Entry,
+}
+
+impl CachedAnswers {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        self.entry.answers.get(&part.number()).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, part: Part, answer: String) {
+        self.entry.answers.insert(part.number(), answer);
+    }
+
+    /// Writes the answers to a temporary file first, so an interrupted run never leaves a torn entry behind.
+    pub fn save(&self) -> Result<()> {
+        let directory = self.path.parent().unwrap_or_else(|| Path::new("."));
+        fs::create_dir_all(directory)
+            .with_context(|| format!("failed to create directory {}", directory.display()))?;
+
+        let temporary = self.path.with_extension("tmp");
+        fs::write(&temporary, serde_json::to_string_pretty(&self.entry)?)
+            .with_context(|| format!("failed to write cache entry {}", temporary.display()))?;
+        fs::rename(&temporary, &self.path)
+            .with_context(|| format!("failed to write cache entry {}", self.path.display()))
+    }
+}
+
+/// Number of cache entries removed by a prune and how many are left.
+#[derive(Debug, Default)]
+pub struct Pruned {
+    pub removed: usize,
+    pub kept: usize,
+}
+
+/// On-disk cache of answers, keyed by the day, its solver version and a hash of the input bytes.
+/// Every input has its own JSON file, so entries can be inspected and deleted by hand.
+pub struct Cache {
+    directory: PathBuf,
+}
+
+impl Cache {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Answers cached for the input. Unreadable entries are treated as missing - the cache
+    /// is only an optimisation, so it never makes a run fail.
+    pub fn answers(&self, day: u8, input: &str) -> Result<CachedAnswers> {
+        let version = days::solver_version(day)?;
+        let path =
+            self.directory
+                .join(format!("day{}_{:016x}.json", day, key(day, version, input)));
+
+        let entry = fs::read_to_string(&path)
+            .ok()
+            .and_then(|entry| serde_json::from_str::<Entry>(&entry).ok())
+            .filter(|entry| entry.day == day && entry.version == version)
+            .unwrap_or(Entry {
+                day,
+                version,
+                answers: BTreeMap::new(),
+            });
+
+        Ok(CachedAnswers { path, entry })
+    }
+
+    /// Removes entries of outdated solver versions, unreadable entries and, if `older_than`
+    /// is given, entries last written longer ago than that.
+    pub fn prune(&self, older_than: Option<Duration>) -> Result<Pruned> {
+        let mut pruned = Pruned::default();
+
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(pruned),
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!(
+                        "failed to read cache directory {}",
+                        self.directory.display()
+                    )
+                })
+            }
+        };
+
+        let now = SystemTime::now();
+        for file in entries {
+            let path = file?.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+
+            let outdated = fs::read_to_string(&path)
+                .ok()
+                .and_then(|entry| serde_json::from_str::<Entry>(&entry).ok())
+                .is_none_or(|entry| days::solver_version(entry.day).ok() != Some(entry.version));
+            let expired = older_than.is_some_and(|older_than| {
+                fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .map(|modified| now.duration_since(modified).unwrap_or_default())
+                    .is_none_or(|age| age >= older_than)
+            });
+
+            if outdated || expired {
+                fs::remove_file(&path)
+                    .with_context(|| format!("failed to remove cache entry {}", path.display()))?;
+                pruned.removed += 1;
+            } else {
+                pruned.kept += 1;
+            }
+        }
+
+        Ok(pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty cache directory of its own for every test.
+    fn scratch(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("aoc-cache-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn write_entry(directory: &Path, name: &str, day: u8, version: u32) {
+        fs::create_dir_all(directory).unwrap();
+        let entry = Entry {
+            day,
+            version,
+            answers: BTreeMap::from([(1, "42".to_owned())]),
+        };
+        fs::write(directory.join(name), serde_json::to_string(&entry).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn keys_are_stable_and_depend_on_the_version() {
+        assert_eq!(fnv1a(*b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(*b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(key(1, 1, "199\n200\n"), key(1, 1, "199\n200\n"));
+        assert_eq!(
+            key(1, 1, "199\n200\n"),
+            fnv1a(*b"\x01\x01\x00\x00\x00199\n200\n")
+        );
+
+        assert_ne!(key(1, 1, "199\n200\n"), key(1, 2, "199\n200\n"));
+        assert_ne!(key(1, 1, "199\n200\n"), key(2, 1, "199\n200\n"));
+        assert_ne!(key(1, 1, "199\n200\n"), key(1, 1, "199\n201\n"));
+    }
+
+    #[test]
+    fn ignores_answers_of_other_versions() -> Result<()> {
+        let directory = scratch("versions");
+        let cache = Cache::new(&directory);
+        let version = days::solver_version(1)?;
+
+        let mut answers = cache.answers(1, "199\n200\n")?;
+        assert_eq!(answers.get(Part::One), None);
+        answers.insert(Part::One, "1".to_owned());
+        answers.save()?;
+        assert_eq!(cache.answers(1, "199\n200\n")?.get(Part::One), Some("1"));
+
+        // An entry of another version, even if found under the current key, is not used.
+        let path = cache.answers(1, "199\n200\n")?.path;
+        write_entry(
+            &directory,
+            path.file_name().unwrap().to_str().unwrap(),
+            1,
+            version + 1,
+        );
+        assert_eq!(cache.answers(1, "199\n200\n")?.get(Part::One), None);
+
+        fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn prunes_outdated_and_unreadable_entries() -> Result<()> {
+        let directory = scratch("prune");
+        let cache = Cache::new(&directory);
+
+        let mut current = cache.answers(1, "199\n200\n")?;
+        current.insert(Part::One, "1".to_owned());
+        current.save()?;
+
+        let version = days::solver_version(1)?;
+        write_entry(&directory, "day1_outdated.json", 1, version + 1);
+        write_entry(&directory, "day99_unknown.json", 99, 1);
+        fs::write(directory.join("day1_torn.json"), "{\"day\": 1, \"vers")?;
+        fs::write(directory.join("notes.txt"), "not an entry")?;
+
+        let pruned = cache.prune(None)?;
+        assert_eq!((pruned.removed, pruned.kept), (3, 1));
+
+        let mut left = fs::read_dir(&directory)?
+            .map(|file| Ok(file?.file_name().into_string().unwrap()))
+            .collect::<Result<Vec<_>>>()?;
+        left.sort();
+        assert_eq!(
+            left,
+            [
+                current.path.file_name().unwrap().to_str().unwrap(),
+                "notes.txt"
+            ]
+        );
+        assert_eq!(cache.answers(1, "199\n200\n")?.get(Part::One), Some("1"));
+
+        let pruned = cache.prune(Some(Duration::ZERO))?;
+        assert_eq!((pruned.removed, pruned.kept), (1, 0));
+
+        fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn prunes_a_missing_directory() -> Result<()> {
+        let pruned = Cache::new(scratch("missing")).prune(None)?;
+        assert_eq!((pruned.removed, pruned.kept), (0, 0));
+
+        Ok(())
+    }
+}
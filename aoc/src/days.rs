@@ -0,0 +1,121 @@
+use crate::bench::{time, Samples};
+use anyhow::{anyhow, Result};
+use utils::frames::{Animated, Palette, Recording};
+use utils::generator::{generate, GeneratedInput};
+use utils::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// Parses the input once and answers requested parts, with answers already formatted for output.
+pub type DaySolver = fn(&str, &[Part]) -> Result<Vec<String>>;
+
+fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Vec<String>> {
+    let parsed = S::parse(input)?;
+
+    parts
+        .iter()
+        .map(|part| match part {
+            Part::One => S::part1(&parsed).map(|answer| answer.to_string()),
+            Part::Two => S::part2(&parsed).map(|answer| answer.to_string()),
+        })
+        .collect()
+}
+
+/// Instantiates a generic function over `Solution` for the given day number.
+macro_rules! for_day {
+    ($day:expr, $function:ident) => {
+        match $day {
+            1 => Some($function::<day1::Day1> as _),
+            2 => Some($function::<day2::Day2> as _),
+            3 => Some($function::<day3::Day3> as _),
+            4 => Some($function::<day4::Day4> as _),
+            5 => Some($function::<day5::Day5> as _),
+            6 => Some($function::<day6::Day6> as _),
+            7 => Some($function::<day7::Day7> as _),
+            8 => Some($function::<day8::Day8> as _),
+            9 => Some($function::<day9::Day9> as _),
+            10 => Some($function::<day10::Day10> as _),
+            11 => Some($function::<day11::Day11> as _),
+            12 => Some($function::<day12::Day12> as _),
+            13 => Some($function::<day13::Day13> as _),
+            14 => Some($function::<day14::Day14> as _),
+            15 => Some($function::<day15::Day15> as _),
+            16 => Some($function::<day16::Day16> as _),
+            17 => Some($function::<day17::Day17> as _),
+            18 => Some($function::<day18::Day18> as _),
+            19 => Some($function::<day19::Day19> as _),
+            20 => Some($function::<day20::Day20> as _),
+            21 => Some($function::<day21::Day21> as _),
+            22 => Some($function::<day22::Day22> as _),
+            23 => Some($function::<day23::Day23> as _),
+            24 => Some($function::<day24::Day24> as _),
+            25 => Some($function::<day25::Day25> as _),
+            _ => None,
+        }
+    };
+}
+
+pub fn solver(day: u8) -> Result<DaySolver> {
+    for_day!(day, solve).ok_or_else(|| anyhow!("there is no puzzle for day {}", day))
+}
+
+fn version<S: Solution>() -> u32 {
+    S::VERSION
+}
+
+/// Version of the day's solver, see [`Solution::VERSION`].
+pub fn solver_version(day: u8) -> Result<u32> {
+    let version: fn() -> u32 =
+        for_day!(day, version).ok_or_else(|| anyhow!("there is no puzzle for day {}", day))?;
+
+    Ok(version())
+}
+
+/// Times parse and both parts of the day over the given number of runs.
+pub type DayTimer = fn(&str, usize) -> Result<Samples>;
+
+pub fn timer(day: u8) -> Result<DayTimer> {
+    for_day!(day, time).ok_or_else(|| anyhow!("there is no puzzle for day {}", day))
+}
+
+/// Generates a random input of the day from a seed and an optional size.
+pub type DayGenerator = fn(u64, Option<usize>) -> GeneratedInput;
+
+pub fn generator(day: u8) -> Result<DayGenerator> {
+    for_day!(day, generate).ok_or_else(|| anyhow!("there is no puzzle for day {}", day))
+}
+
+/// Parses the input and records the evolution of the day's grid, for the given number of steps
+/// or as long as the puzzle needs. Comes with the day's default palette.
+pub type DayRecorder = fn(&str, Option<usize>) -> Result<(Recording, Palette)>;
+
+fn record<A: Animated>(input: &str, steps: Option<usize>) -> Result<(Recording, Palette)> {
+    let parsed = A::parse(input)?;
+
+    Ok((A::record(&parsed, steps)?, A::palette()))
+}
+
+pub fn recorder(day: u8) -> Result<DayRecorder> {
+    match day {
+        11 => Ok(record::<day11::Day11>),
+        20 => Ok(record::<day20::Day20>),
+        25 => Ok(record::<day25::Day25>),
+        _ => Err(anyhow!(
+            "day {} cannot be recorded, only days 11, 20 and 25 can",
+            day
+        )),
+    }
+}
@@ -0,0 +1,569 @@
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use utils::frames::Palette;
+use utils::{input, instrument, parser};
+
+mod bench;
+mod cache;
+mod days;
+mod memory;
+mod parallel;
+mod verify;
+use bench::{DayReport, Report};
+use cache::Cache;
+use days::Part;
+use memory::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "usage: aoc run <day> [--input <path|->] [--part <1|2>] [--no-cache]
+       aoc cache-prune [--older-than <days>] [--all]
+       aoc bench [<day>...] [--runs <n>] [--json <path>]
+       aoc verify [<day>...]
+       aoc all [<day>...] [--threads <n>]
+       aoc generate <day> [--seed <n>] [--size <n>] [--count <n>] [--output <dir>]
+       aoc record <11|20|25> --output <path> [--input <path|->] [--steps <n>] [--format <gif|pbm|pgm>]
+                  [--scale <n>] [--palette <rrggbb,...>] [--delay <cs>]";
+
+struct RunOptions {
+    day: u8,
+    input: PathBuf,
+    parts: Vec<Part>,
+    cached: bool,
+}
+
+impl RunOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let day: u8 = args
+            .next()
+            .ok_or_else(|| anyhow!("missing day number\n{}", USAGE))?
+            .parse()
+            .context("day has to be a number")?;
+
+        let mut input = None;
+        let mut parts = vec![Part::One, Part::Two];
+        let mut cached = true;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => {
+                    input = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--input requires a path"))?
+                            .into(),
+                    );
+                }
+                "--part" | "-p" => {
+                    let part = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--part requires 1 or 2"))?;
+                    parts = match part.as_str() {
+                        "1" => vec![Part::One],
+                        "2" => vec![Part::Two],
+                        _ => return Err(anyhow!("unknown part {}, expected 1 or 2", part)),
+                    };
+                }
+                "--no-cache" => cached = false,
+                _ => return Err(anyhow!("unknown argument {}\n{}", arg, USAGE)),
+            }
+        }
+
+        Ok(Self {
+            day,
+            input: input.unwrap_or_else(|| format!("day{}/input", day).into()),
+            parts,
+            cached,
+        })
+    }
+}
+
+struct BenchOptions {
+    days: Vec<u8>,
+    runs: usize,
+    json: Option<PathBuf>,
+}
+
+impl BenchOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut days = vec![];
+        let mut runs = 5;
+        let mut json = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--runs" | "-r" => {
+                    runs = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--runs requires a number"))?
+                        .parse()
+                        .context("number of runs has to be a number")?;
+
+                    if runs == 0 {
+                        return Err(anyhow!("--runs has to be at least 1"));
+                    }
+                }
+                "--json" | "-j" => {
+                    json = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--json requires a path"))?
+                            .into(),
+                    );
+                }
+                day => days.push(
+                    day.parse()
+                        .with_context(|| format!("unknown argument {}\n{}", day, USAGE))?,
+                ),
+            }
+        }
+
+        if days.is_empty() {
+            days = (1..=25).collect();
+        }
+
+        Ok(Self { days, runs, json })
+    }
+}
+
+fn bench(options: BenchOptions) -> Result<()> {
+    let mut reports = vec![];
+
+    for day in options.days.iter().copied() {
+        let timer = days::timer(day)?;
+        let path = format!("day{}/input", day);
+        let input =
+            fs::read_to_string(&path).with_context(|| format!("failed to read input {}", path))?;
+
+        let samples = timer(&input, options.runs)
+            .map_err(|err| parser::in_file(err, &path))
+            .with_context(|| format!("day {} failed", day))?;
+        reports.push(DayReport::new(day, &samples));
+    }
+
+    let report = Report::new(options.runs, reports);
+    print!("{}", report.table());
+
+    if let Some(json) = options.json {
+        fs::write(&json, report.to_json()?)
+            .with_context(|| format!("failed to write report {}", json.display()))?;
+    }
+
+    Ok(())
+}
+
+fn verify(args: impl Iterator<Item = String>) -> Result<()> {
+    let mut days = args
+        .map(|day| {
+            day.parse()
+                .with_context(|| format!("unknown argument {}\n{}", day, USAGE))
+        })
+        .collect::<Result<Vec<u8>>>()?;
+
+    if days.is_empty() {
+        days = (1..=25).collect();
+    }
+
+    let mut checked = 0;
+    let mut failed = 0;
+    for day in days {
+        let summary = verify::verify_day(day)?;
+        checked += summary.checked;
+        failed += summary.failed;
+    }
+
+    println!("{} of {} answers correct", checked - failed, checked);
+
+    if failed > 0 {
+        Err(anyhow!("{} of {} answers did not match", failed, checked))
+    } else {
+        Ok(())
+    }
+}
+
+struct GenerateOptions {
+    day: u8,
+    seed: Option<u64>,
+    size: Option<usize>,
+    count: u64,
+    output: Option<PathBuf>,
+}
+
+impl GenerateOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let day: u8 = args
+            .next()
+            .ok_or_else(|| anyhow!("missing day number\n{}", USAGE))?
+            .parse()
+            .context("day has to be a number")?;
+
+        let mut seed = None;
+        let mut size = None;
+        let mut count = 1;
+        let mut output = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" | "-s" => {
+                    seed = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--seed requires a number"))?
+                            .parse()
+                            .context("seed has to be a number")?,
+                    );
+                }
+                "--size" => {
+                    size = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--size requires a number"))?
+                            .parse()
+                            .context("size has to be a number")?,
+                    );
+                }
+                "--count" | "-c" => {
+                    count = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--count requires a number"))?
+                        .parse()
+                        .context("count has to be a number")?;
+
+                    if count == 0 {
+                        return Err(anyhow!("--count has to be at least 1"));
+                    }
+                }
+                "--output" | "-o" => {
+                    output = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--output requires a directory"))?
+                            .into(),
+                    );
+                }
+                _ => return Err(anyhow!("unknown argument {}\n{}", arg, USAGE)),
+            }
+        }
+
+        if count > 1 && output.is_none() {
+            return Err(anyhow!("--count requires --output"));
+        }
+
+        Ok(Self {
+            day,
+            seed,
+            size,
+            count,
+            output,
+        })
+    }
+}
+
+fn generate(options: GenerateOptions) -> Result<()> {
+    let generator = days::generator(options.day)?;
+    let first_seed = match options.seed {
+        Some(seed) => seed,
+        None => {
+            let seed = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)?
+                .as_nanos() as u64;
+            eprintln!("seed: {}", seed);
+            seed
+        }
+    };
+
+    let output = match options.output {
+        Some(output) => output,
+        None => {
+            print!("{}", generator(first_seed, options.size).input);
+            return Ok(());
+        }
+    };
+
+    fs::create_dir_all(&output)
+        .with_context(|| format!("failed to create directory {}", output.display()))?;
+
+    for seed in (0..options.count).map(|offset| first_seed.wrapping_add(offset)) {
+        let generated = generator(seed, options.size);
+        let name = format!("day{}_seed{}", options.day, seed);
+
+        let path = output.join(format!("{}.txt", name));
+        fs::write(&path, generated.input)
+            .with_context(|| format!("failed to write input {}", path.display()))?;
+
+        if let Some(ground_truth) = generated.ground_truth {
+            let path = output.join(format!("{}.truth.txt", name));
+            fs::write(&path, ground_truth)
+                .with_context(|| format!("failed to write ground truth {}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+enum FrameFormat {
+    Gif,
+    Pbm,
+    Pgm,
+}
+
+struct RecordOptions {
+    day: u8,
+    input: PathBuf,
+    output: PathBuf,
+    steps: Option<usize>,
+    format: FrameFormat,
+    scale: usize,
+    palette: Option<Palette>,
+    delay: u16,
+}
+
+impl RecordOptions {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let day: u8 = args
+            .next()
+            .ok_or_else(|| anyhow!("missing day number\n{}", USAGE))?
+            .parse()
+            .context("day has to be a number")?;
+
+        let mut input = None;
+        let mut output = None;
+        let mut steps = None;
+        let mut format = FrameFormat::Gif;
+        let mut scale = 4;
+        let mut palette = None;
+        let mut delay = 10;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => {
+                    input = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--input requires a path"))?
+                            .into(),
+                    );
+                }
+                "--output" | "-o" => {
+                    output = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--output requires a path"))?
+                            .into(),
+                    );
+                }
+                "--steps" => {
+                    steps = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--steps requires a number"))?
+                            .parse()
+                            .context("number of steps has to be a number")?,
+                    );
+                }
+                "--format" | "-f" => {
+                    let name = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--format requires gif, pbm or pgm"))?;
+                    format = match name.as_str() {
+                        "gif" => FrameFormat::Gif,
+                        "pbm" => FrameFormat::Pbm,
+                        "pgm" => FrameFormat::Pgm,
+                        _ => {
+                            return Err(anyhow!(
+                                "unknown format {}, expected gif, pbm or pgm",
+                                name
+                            ))
+                        }
+                    };
+                }
+                "--scale" => {
+                    scale = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--scale requires a number"))?
+                        .parse()
+                        .context("scale has to be a number")?;
+                }
+                "--palette" => {
+                    palette = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--palette requires a list of colours"))?
+                            .parse()?,
+                    );
+                }
+                "--delay" => {
+                    delay = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--delay requires a number"))?
+                        .parse()
+                        .context("delay has to be a number of hundredths of a second")?;
+                }
+                _ => return Err(anyhow!("unknown argument {}\n{}", arg, USAGE)),
+            }
+        }
+
+        Ok(Self {
+            day,
+            input: input.unwrap_or_else(|| format!("day{}/input", day).into()),
+            output: output.ok_or_else(|| anyhow!("missing --output\n{}", USAGE))?,
+            steps,
+            format,
+            scale,
+            palette,
+            delay,
+        })
+    }
+}
+
+fn record(options: RecordOptions) -> Result<()> {
+    let recorder = days::recorder(options.day)?;
+    let input = input::read(&options.input)?;
+
+    let (recording, palette) =
+        recorder(&input, options.steps).map_err(|err| parser::in_file(err, &options.input))?;
+    let palette = options.palette.unwrap_or(palette);
+
+    match options.format {
+        FrameFormat::Gif => {
+            recording.write_gif(&options.output, options.scale, &palette, options.delay)?
+        }
+        FrameFormat::Pbm => recording.write_pbm(&options.output, options.scale, &palette)?,
+        FrameFormat::Pgm => recording.write_pgm(&options.output, options.scale, &palette)?,
+    }
+
+    eprintln!("recorded {} frames", recording.frames().len());
+
+    Ok(())
+}
+
+fn all(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut days = vec![];
+    let mut threads = thread::available_parallelism().map_or(1, usize::from);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threads" | "-t" => {
+                threads = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--threads requires a number"))?
+                    .parse()
+                    .context("number of threads has to be a number")?;
+
+                if threads == 0 {
+                    return Err(anyhow!("--threads has to be at least 1"));
+                }
+            }
+            day => days.push(
+                day.parse()
+                    .with_context(|| format!("unknown argument {}\n{}", day, USAGE))?,
+            ),
+        }
+    }
+
+    if days.is_empty() {
+        days = (1..=25).collect();
+    }
+
+    let summary = parallel::solve_all(&days, threads);
+    print!("{}", summary.table());
+
+    match summary.failed() {
+        0 => Ok(()),
+        failed => Err(anyhow!("{} parts failed", failed)),
+    }
+}
+
+fn cache_prune(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut older_than = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--older-than" => {
+                let days: u64 = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--older-than requires a number of days"))?
+                    .parse()
+                    .context("age has to be a number of days")?;
+                let seconds = days
+                    .checked_mul(24 * 60 * 60)
+                    .ok_or_else(|| anyhow!("age of {} days is too large", days))?;
+                older_than = Some(Duration::from_secs(seconds));
+            }
+            "--all" => older_than = Some(Duration::ZERO),
+            _ => return Err(anyhow!("unknown argument {}\n{}", arg, USAGE)),
+        }
+    }
+
+    let pruned = Cache::new(cache::DIRECTORY).prune(older_than)?;
+    println!(
+        "removed {} cache entries, {} left",
+        pruned.removed, pruned.kept
+    );
+
+    Ok(())
+}
+
+fn run(options: RunOptions) -> Result<()> {
+    let input = input::read(&options.input)?;
+
+    let solver = days::solver(options.day)?;
+    let mut cached = Cache::new(cache::DIRECTORY).answers(options.day, &input)?;
+    let unsolved = options
+        .parts
+        .iter()
+        .copied()
+        .filter(|part| !options.cached || cached.get(*part).is_none())
+        .collect::<Vec<_>>();
+
+    if !unsolved.is_empty() {
+        let (answers, usage) = memory::measure(|| solver(&input, &unsolved));
+        let answers = answers.map_err(|err| parser::in_file(err, &options.input))?;
+        usage.instrument();
+
+        for (part, answer) in unsolved.iter().copied().zip(answers) {
+            cached.insert(part, answer);
+        }
+
+        if let Err(err) = cached.save() {
+            eprintln!("warning: answers were not cached: {:#}", err);
+        }
+    }
+
+    if unsolved.len() < options.parts.len() {
+        eprintln!("using cached answers, --no-cache solves the input again");
+    }
+
+    for part in options.parts.iter() {
+        let answer = cached.get(*part).unwrap_or_default();
+
+        // Some answers (e.g. day 13) are rendered pictures, so they start on a line of their own.
+        let separator = if answer.contains('\n') { "\n" } else { " " };
+        println!(
+            "day {} part {}:{}{}",
+            options.day,
+            part.number(),
+            separator,
+            answer.trim_end()
+        );
+    }
+
+    let report = instrument::take_report();
+    if !report.is_empty() {
+        eprint!("{}", report);
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let mut args = env::args().skip(1);
+
+    match args.next().as_deref() {
+        Some("run") => run(RunOptions::from_args(args)?),
+        Some("bench") => bench(BenchOptions::from_args(args)?),
+        Some("verify") => verify(args),
+        Some("all") => all(args),
+        Some("cache-prune") => cache_prune(args),
+        Some("generate") => generate(GenerateOptions::from_args(args)?),
+        Some("record") => record(RecordOptions::from_args(args)?),
+        Some(command) => Err(anyhow!("unknown command {}\n{}", command, USAGE)),
+        None => Err(anyhow!(USAGE)),
+    }
+}
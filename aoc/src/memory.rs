@@ -0,0 +1,93 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use utils::instrument;
+
+thread_local! {
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// System allocator which keeps track of how much memory every thread holds, its peak
+/// and the number of allocations.
+/// Memory freed by another thread than the one which allocated it is not accounted for precisely.
+pub struct CountingAllocator;
+
+fn grow(size: usize) {
+    // Thread locals may already be gone while the thread is shutting down.
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+    let _ = CURRENT.try_with(|current| {
+        let now = current.get() + size;
+        current.set(now);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+    });
+}
+
+fn shrink(size: usize) {
+    let _ = CURRENT.try_with(|current| current.set(current.get().saturating_sub(size)));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Memory used by a piece of code on the current thread.
+#[derive(Debug, Clone, Copy)]
+pub struct Usage {
+    /// Most bytes held allocated at once.
+    pub peak_bytes: usize,
+    /// Number of allocations and reallocations.
+    pub allocations: u64,
+}
+
+/// Runs the function and measures its memory usage on this thread.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let baseline = CURRENT.with(Cell::get);
+    let allocations = ALLOCATIONS.with(Cell::get);
+    PEAK.with(|peak| peak.set(baseline));
+
+    let result = f();
+
+    let usage = Usage {
+        peak_bytes: PEAK.with(Cell::get) - baseline,
+        allocations: ALLOCATIONS.with(Cell::get) - allocations,
+    };
+
+    (result, usage)
+}
+
+impl Usage {
+    /// Adds the usage to instrumentation metrics, if they are collected.
+    pub fn instrument(&self) {
+        instrument::add("memory.allocations", self.allocations);
+        instrument::maximum("memory.peak_bytes", self.peak_bytes as u64);
+    }
+}
@@ -0,0 +1,222 @@
+use anyhow::{anyhow, Result};
+use std::any::Any;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use utils::instrument::{self, Report};
+use utils::{input, parser};
+
+use crate::bench::human;
+use crate::days::{self, Part};
+use crate::memory;
+
+/// Answer of one part, with how long it took and how much memory it needed at peak,
+/// both including reading and parsing the input. Metrics are only collected with the `instrument` feature.
+struct PartOutcome {
+    answer: Result<String>,
+    time: Duration,
+    peak_bytes: usize,
+    metrics: Report,
+}
+
+struct DayOutcome {
+    day: u8,
+    parts: Vec<PartOutcome>,
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
+
+/// Solves one part. A panicking solver is reported as a failed part instead of taking
+/// the whole pool down with it.
+fn solve_part(day: u8, part: Part) -> PartOutcome {
+    // Worker threads are reused, start without metrics of the previous job.
+    instrument::take_report();
+
+    let start = Instant::now();
+    let (answer, usage) = memory::measure(|| {
+        panic::catch_unwind(|| -> Result<String> {
+            let solver = days::solver(day)?;
+            let path = format!("day{}/input", day);
+            let input = input::read(&path)?;
+
+            let answers = solver(&input, &[part]).map_err(|err| parser::in_file(err, &path))?;
+            Ok(answers.into_iter().next().unwrap_or_default())
+        })
+        .unwrap_or_else(|payload| Err(anyhow!("panicked: {}", panic_message(&*payload))))
+    });
+
+    PartOutcome {
+        answer,
+        time: start.elapsed(),
+        peak_bytes: usage.peak_bytes,
+        metrics: {
+            usage.instrument();
+            instrument::take_report()
+        },
+    }
+}
+
+/// Outcome of solving many days at once.
+pub struct Summary {
+    days: Vec<DayOutcome>,
+    threads: usize,
+    wall_time: Duration,
+}
+
+/// Solves both parts of every day on a pool of `threads` worker threads. Every part is a job
+/// of its own, so a slow day keeps just one worker busy (two if both of its parts are slow)
+/// while the rest of the pool carries on with other days.
+pub fn solve_all(days: &[u8], threads: usize) -> Summary {
+    let jobs = days
+        .iter()
+        .flat_map(|&day| [(day, Part::One), (day, Part::Two)])
+        .collect::<Vec<_>>();
+    let next_job = AtomicUsize::new(0);
+    let finished = Mutex::new(Vec::with_capacity(jobs.len()));
+
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                while let Some(&(day, part)) = jobs.get(next_job.fetch_add(1, Ordering::Relaxed)) {
+                    let outcome = solve_part(day, part);
+                    finished
+                        .lock()
+                        .expect("worker panicked while holding results")
+                        .push((day, part, outcome));
+                }
+            });
+        }
+    });
+    let wall_time = start.elapsed();
+
+    let mut finished = finished
+        .into_inner()
+        .expect("worker panicked while holding results");
+    finished.sort_by_key(|(day, part, _)| (*day, part.number()));
+
+    let mut outcomes: Vec<DayOutcome> = vec![];
+    for (day, _, outcome) in finished {
+        match outcomes.last_mut() {
+            Some(last) if last.day == day => last.parts.push(outcome),
+            _ => outcomes.push(DayOutcome {
+                day,
+                parts: vec![outcome],
+            }),
+        }
+    }
+
+    Summary {
+        days: outcomes,
+        threads: threads.max(1),
+        wall_time,
+    }
+}
+
+impl Summary {
+    pub fn failed(&self) -> usize {
+        self.days
+            .iter()
+            .flat_map(|day| day.parts.iter())
+            .filter(|part| part.answer.is_err())
+            .count()
+    }
+
+    /// One row per day with both answers, time of each part and the higher of the two allocation peaks.
+    /// Errors and collected metrics are listed below the table.
+    pub fn table(&self) -> String {
+        let answer = |part: &PartOutcome| match &part.answer {
+            Ok(answer) if answer.trim_end().contains('\n') => {
+                format!("({} lines)", answer.trim_end().lines().count())
+            }
+            Ok(answer) => answer.clone(),
+            Err(_) => "FAILED".to_owned(),
+        };
+        let width = self
+            .days
+            .iter()
+            .flat_map(|day| day.parts.iter().map(answer))
+            .map(|answer| answer.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max("part 1".len());
+
+        let mut table = format!(
+            "{:>3}  {:<width$}  {:>9}  {:<width$}  {:>9}  {:>10}\n",
+            "day",
+            "part 1",
+            "time",
+            "part 2",
+            "time",
+            "peak mem",
+            width = width
+        );
+        let mut errors = String::new();
+        let mut metrics = String::new();
+
+        for day in self.days.iter() {
+            table.push_str(&format!("{:>3}", day.day));
+
+            for (number, part) in (1..).zip(day.parts.iter()) {
+                table.push_str(&format!(
+                    "  {:<width$}  {:>9}",
+                    answer(part),
+                    human(part.time),
+                    width = width
+                ));
+
+                if let Err(err) = &part.answer {
+                    errors.push_str(&format!("day {}: {:#}\n", day.day, err));
+                }
+
+                if !part.metrics.is_empty() {
+                    metrics.push_str(&format!(
+                        "\nday {} part {}:\n{}",
+                        day.day, number, part.metrics
+                    ));
+                }
+            }
+
+            let peak = day.parts.iter().map(|part| part.peak_bytes).max();
+            table.push_str(&format!("  {:>10}\n", bytes(peak.unwrap_or_default())));
+        }
+
+        let busy_time = self
+            .days
+            .iter()
+            .flat_map(|day| day.parts.iter())
+            .map(|part| part.time)
+            .sum::<Duration>();
+        table.push_str(&format!(
+            "wall time {} on {} thread{}, {} of work\n",
+            human(self.wall_time),
+            self.threads,
+            if self.threads == 1 { "" } else { "s" },
+            human(busy_time)
+        ));
+        table.push_str(&errors);
+        table.push_str(&metrics);
+
+        table
+    }
+}
+
+fn bytes(bytes: usize) -> String {
+    if bytes < 1 << 10 {
+        format!("{}B", bytes)
+    } else if bytes < 1 << 20 {
+        format!("{:.1}KiB", bytes as f64 / (1u64 << 10) as f64)
+    } else if bytes < 1 << 30 {
+        format!("{:.1}MiB", bytes as f64 / (1u64 << 20) as f64)
+    } else {
+        format!("{:.2}GiB", bytes as f64 / (1u64 << 30) as f64)
+    }
+}
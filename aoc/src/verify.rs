@@ -0,0 +1,145 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use utils::parser;
+
+use crate::days::{self, Part};
+
+/// Answer as written in a manifest - a number, which may be negative, or text.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(number) => write!(f, "{}", number),
+            Answer::Signed(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Expected answers for one input. A part is left out when its answer is not known,
+/// e.g. the day 16 examples only come with part 1 answers.
+#[derive(Debug, Deserialize)]
+struct Expected {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// Contents of `dayX/answers.json` - expected answers keyed by input file name.
+type Manifest = BTreeMap<String, Expected>;
+
+fn load_manifest(day: u8) -> Result<Manifest> {
+    let path = format!("day{}/answers.json", day);
+    let manifest = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read answer manifest {}", path))?;
+
+    serde_json::from_str(&manifest).with_context(|| format!("malformed answer manifest {}", path))
+}
+
+/// Number of checked answers and how many of them did not match.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub checked: usize,
+    pub failed: usize,
+}
+
+/// Runs the day against every input listed in its manifest, printing one line per answer.
+pub fn verify_day(day: u8) -> Result<Summary> {
+    let solver = days::solver(day)?;
+    let mut summary = Summary::default();
+
+    for (file, expected) in load_manifest(day)? {
+        let expectations = [(Part::One, expected.part1), (Part::Two, expected.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some((part, answer?.to_string())))
+            .collect::<Vec<_>>();
+        let parts = expectations
+            .iter()
+            .map(|(part, _)| *part)
+            .collect::<Vec<_>>();
+
+        let path = format!("day{}/{}", day, file);
+        let answers = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read input {}", path))
+            .and_then(|input| solver(&input, &parts).map_err(|err| parser::in_file(err, &path)));
+
+        let answers = match answers {
+            Ok(answers) => answers,
+            Err(err) => {
+                println!("day {} {}: FAILED - {:#}", day, file, err);
+                summary.checked += expectations.len();
+                summary.failed += expectations.len();
+                continue;
+            }
+        };
+
+        for ((part, expected), answer) in expectations.iter().zip(answers) {
+            summary.checked += 1;
+
+            if answer.trim_end() == expected.trim_end() {
+                println!("day {} {} part {}: ok", day, file, part.number());
+            } else {
+                summary.failed += 1;
+                println!(
+                    "day {} {} part {}: MISMATCH - expected {}, got {}",
+                    day,
+                    file,
+                    part.number(),
+                    multiline(expected),
+                    multiline(&answer)
+                );
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+fn multiline(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}\n", answer.trim_end())
+    } else {
+        answer.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(manifest: &str) -> (Option<String>, Option<String>) {
+        let expected: Expected = serde_json::from_str(manifest).unwrap();
+        (
+            expected.part1.map(|answer| answer.to_string()),
+            expected.part2.map(|answer| answer.to_string()),
+        )
+    }
+
+    #[test]
+    fn reads_numbers_of_either_sign_and_text() {
+        assert_eq!(
+            expected(r#"{"part1": -1690020, "part2": 18446744073709551615}"#),
+            (
+                Some("-1690020".to_owned()),
+                Some("18446744073709551615".to_owned())
+            )
+        );
+        assert_eq!(
+            expected(r#"{"part1": -9223372036854775808, "part2": "\n# #\n"}"#),
+            (
+                Some("-9223372036854775808".to_owned()),
+                Some("\n# #\n".to_owned())
+            )
+        );
+        assert_eq!(expected(r#"{"part2": 0}"#), (None, Some("0".to_owned())));
+    }
+}
@@ -0,0 +1,22 @@
+use crate::Day1;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day1 {
+    const DEFAULT_SIZE: usize = 2000;
+
+    /// `size` depth measurements of a sea floor which slowly gets deeper, with occasional dips.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let mut depth: usize = rng.gen_range(100..200);
+
+        (0..size)
+            .map(|_| {
+                let measurement = depth;
+                depth = depth.saturating_add_signed(rng.gen_range(-10..=15));
+                format!("{}\n", measurement)
+            })
+            .collect::<String>()
+            .into()
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use std::cmp::Ordering;
+use utils::windows::WindowExt;
+use utils::Solution;
+
+mod generator;
+mod monitor;
+mod profile;
+
+pub use monitor::SonarMonitor;
+pub use profile::{DepthProfile, Span};
+
+/// How sums of neighbouring sliding windows compare to each other.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WindowChanges {
+    pub increases: usize,
+    pub decreases: usize,
+    pub unchanged: usize,
+}
+
+impl WindowChanges {
+    /// Counts the change of the window sum when `leaving` drops out of the window and `entering` comes in.
+    fn record(&mut self, leaving: usize, entering: usize) {
+        match entering.cmp(&leaving) {
+            Ordering::Greater => self.increases += 1,
+            Ordering::Less => self.decreases += 1,
+            Ordering::Equal => self.unchanged += 1,
+        }
+    }
+}
+
+/// Compares sums of every pair of neighbouring windows of `size` measurements. Size 1 compares
+/// single measurements. Panics if `size` is 0.
+///
+/// Neighbouring windows share all measurements but the first one of the earlier window and the last
+/// one of the later window, so instead of summing windows it is enough to compare measurement `i`
+/// with measurement `i + size`. This takes O(n) time regardless of the window size.
+pub fn window_changes(sonar_data: impl IntoIterator<Item = usize>, size: usize) -> WindowChanges {
+    assert!(size > 0, "window size has to be at least 1");
+
+    sonar_data.into_iter().lagged(size).fold(
+        WindowChanges::default(),
+        |mut changes, (leaving, entering)| {
+            changes.record(leaving, entering);
+            changes
+        },
+    )
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input
+            .lines()
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn part1(sonar_data: &Self::Input) -> Result<Self::Part1> {
+        Ok(window_changes(sonar_data.iter().copied(), 1).increases)
+    }
+
+    fn part2(sonar_data: &Self::Input) -> Result<Self::Part2> {
+        Ok(window_changes(sonar_data.iter().copied(), 3).increases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [usize; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    fn changes(increases: usize, decreases: usize, unchanged: usize) -> WindowChanges {
+        WindowChanges {
+            increases,
+            decreases,
+            unchanged,
+        }
+    }
+
+    /// Sums every window and compares neighbouring sums directly.
+    fn brute_force(sonar_data: &[usize], size: usize) -> WindowChanges {
+        let sums = sonar_data
+            .windows(size)
+            .map(|window| window.iter().sum())
+            .collect::<Vec<usize>>();
+
+        sums.windows(2)
+            .fold(WindowChanges::default(), |mut changes, pair| {
+                changes.record(pair[0], pair[1]);
+                changes
+            })
+    }
+
+    #[test]
+    fn counts_changes_of_the_example() {
+        assert_eq!(window_changes(EXAMPLE, 1), changes(7, 2, 0));
+        assert_eq!(window_changes(EXAMPLE, 3), changes(5, 1, 1));
+    }
+
+    #[test]
+    fn counts_changes_for_other_window_sizes() {
+        // Window sums 399, 408, 418, 410, 407, 447, 509, 529, 523.
+        assert_eq!(window_changes(EXAMPLE, 2), changes(5, 3, 0));
+        // Window sums 1017, 1035, 1065, 1126, 1176, 1239.
+        assert_eq!(window_changes(EXAMPLE, 5), changes(5, 0, 0));
+
+        let sonar_data = [5, 5, 3, 8, 8, 2, 9, 9, 9, 1, 4, 4];
+        for size in 1..=sonar_data.len() {
+            assert_eq!(
+                window_changes(sonar_data, size),
+                brute_force(&sonar_data, size),
+                "size {}",
+                size
+            );
+        }
+    }
+
+    #[test]
+    fn windows_larger_than_the_input_have_nothing_to_compare() {
+        assert_eq!(window_changes(EXAMPLE, 10), WindowChanges::default());
+        assert_eq!(window_changes(EXAMPLE, 11), WindowChanges::default());
+        assert_eq!(window_changes(EXAMPLE, 100), WindowChanges::default());
+        assert_eq!(window_changes([], 3), WindowChanges::default());
+    }
+}
@@ -1,43 +1,110 @@
-use std::error::Error;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-use utils::consecutive_pairs;
-
-fn measure_increase(total: usize, (current, next): (usize, usize)) -> usize {
-    if next > current {
-        total + 1
-    } else {
-        total
-    }
-}
+use anyhow::{anyhow, Context, Result};
+use day1::{Day1, DepthProfile, SonarMonitor};
+use std::env;
+use utils::input;
+use utils::Solution;
 
-fn measurement_increases(sonar_data: &[usize]) -> usize {
-    let point_pairs = consecutive_pairs(sonar_data.iter());
+const USAGE: &str = "usage: day1 [<path|->] [--report <text|csv> | --follow] [--window <n>]";
 
-    point_pairs.fold(0, |total, (current, next)| {
-        measure_increase(total, (*current, *next))
-    })
+enum ReportFormat {
+    Text,
+    Csv,
 }
 
-fn measurement_window_increases(sonar_data: &[usize]) -> usize {
-    let window_sums = consecutive_pairs(sonar_data.windows(3));
+/// Prints updated totals after every reading, as the readings are written.
+fn monitor(path: &str, window: usize) -> Result<()> {
+    let mut monitor = SonarMonitor::new(window);
+
+    for line in input::follow(path)? {
+        let line = line.context("failed to read input")?;
+        let reading = line.trim();
+        if reading.is_empty() {
+            continue;
+        }
+
+        monitor.ingest(
+            reading
+                .parse()
+                .with_context(|| format!("invalid depth reading {}", reading))?,
+        );
+        println!(
+            "{} readings: {} increases, {} window increases",
+            monitor.readings(),
+            monitor.changes().increases,
+            monitor.window_changes().increases
+        );
+    }
 
-    window_sums.fold(0, |total, (window, next_window)| {
-        measure_increase(total, (window.iter().sum(), next_window.iter().sum()))
-    })
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let sonar_data: Result<Vec<usize>, Box<dyn Error>> = BufReader::new(File::open("./input")?)
-        .lines()
-        .map(|line| {
-            line.map_err(Into::into)
-                .and_then(|text| text.parse::<usize>().map_err(Into::into))
-        })
-        .collect();
-    let sonar_data = sonar_data?;
-
-    println!("{}", measurement_increases(&sonar_data));
-    println!("{}", measurement_window_increases(&sonar_data));
+fn main() -> Result<()> {
+    let mut path = None;
+    let mut report = None;
+    let mut follow = false;
+    let mut window = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--report" => {
+                let format = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--report requires text or csv"))?;
+                report = match format.as_str() {
+                    "text" => Some(ReportFormat::Text),
+                    "csv" => Some(ReportFormat::Csv),
+                    _ => return Err(anyhow!("unknown format {}, expected text or csv", format)),
+                };
+            }
+            "--follow" | "-f" => follow = true,
+            "--window" => {
+                let size = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--window requires a number"))?
+                    .parse()
+                    .context("window size has to be a number")?;
+
+                if size == 0 {
+                    return Err(anyhow!("--window has to be at least 1"));
+                }
+                window = Some(size);
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(anyhow!("unknown argument {}\n{}", arg, USAGE)),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| "./input".into());
+    if window.is_some() && report.is_none() && !follow {
+        return Err(anyhow!(
+            "--window only applies to --report and --follow\n{}",
+            USAGE
+        ));
+    }
+    let window = window.unwrap_or(3);
+
+    if follow {
+        if report.is_some() {
+            return Err(anyhow!(
+                "--follow cannot be combined with --report\n{}",
+                USAGE
+            ));
+        }
+
+        return monitor(&path, window);
+    }
+
+    let sonar_data = Day1::parse_reader(input::open(&path)?)?;
+
+    match report {
+        Some(ReportFormat::Text) => print!("{}", DepthProfile::new(&sonar_data, window)),
+        Some(ReportFormat::Csv) => print!("{}", DepthProfile::new(&sonar_data, window).to_csv()),
+        None => {
+            println!("{}", Day1::part1(&sonar_data)?);
+            println!("{}", Day1::part2(&sonar_data)?);
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+
+use crate::WindowChanges;
+
+/// Counts sonar increases incrementally, one reading at a time. Only the last `window` readings
+/// are kept, so it can watch an endless stream of readings.
+#[derive(Debug, Clone)]
+pub struct SonarMonitor {
+    window: usize,
+    recent: VecDeque<usize>,
+    readings: usize,
+    changes: WindowChanges,
+    window_changes: WindowChanges,
+}
+
+impl SonarMonitor {
+    /// Panics if `window` is 0.
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "window size has to be at least 1");
+
+        Self {
+            window,
+            recent: VecDeque::with_capacity(window),
+            readings: 0,
+            changes: WindowChanges::default(),
+            window_changes: WindowChanges::default(),
+        }
+    }
+
+    pub fn ingest(&mut self, depth: usize) {
+        if let Some(previous) = self.recent.back() {
+            self.changes.record(*previous, depth);
+        }
+
+        if self.recent.len() == self.window {
+            if let Some(leaving) = self.recent.pop_front() {
+                self.window_changes.record(leaving, depth);
+            }
+        }
+
+        self.recent.push_back(depth);
+        self.readings += 1;
+    }
+
+    pub fn readings(&self) -> usize {
+        self.readings
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Changes between consecutive readings so far - the increases are the answer to part 1.
+    pub fn changes(&self) -> WindowChanges {
+        self.changes
+    }
+
+    /// Changes between consecutive window sums so far - with the default window of 3 readings
+    /// the increases are the answer to part 2.
+    pub fn window_changes(&self) -> WindowChanges {
+        self.window_changes
+    }
+}
+
+impl Default for SonarMonitor {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window_changes;
+
+    const EXAMPLE: [usize; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    /// Checks the monitor against [`window_changes`] of every prefix of `sonar_data`.
+    fn assert_matches_batch(sonar_data: &[usize], window: usize) {
+        let mut monitor = SonarMonitor::new(window);
+
+        for (idx, depth) in sonar_data.iter().copied().enumerate() {
+            monitor.ingest(depth);
+            let seen = sonar_data[..=idx].iter().copied();
+
+            assert_eq!(monitor.readings(), idx + 1);
+            assert_eq!(
+                monitor.changes(),
+                window_changes(seen.clone(), 1),
+                "reading {}",
+                idx
+            );
+            assert_eq!(
+                monitor.window_changes(),
+                window_changes(seen, window),
+                "reading {}",
+                idx
+            );
+        }
+    }
+
+    #[test]
+    fn matches_batch_counts_after_every_reading() {
+        assert_matches_batch(&EXAMPLE, 3);
+
+        let mut monitor = SonarMonitor::default();
+        EXAMPLE.into_iter().for_each(|depth| monitor.ingest(depth));
+        assert_eq!(monitor.changes().increases, 7);
+        assert_eq!(monitor.window_changes().increases, 5);
+    }
+
+    #[test]
+    fn matches_batch_counts_for_other_window_sizes() {
+        let sonar_data = [5, 5, 3, 8, 8, 2, 9, 9, 9, 1, 4, 4];
+
+        for window in [1, 2, 4, 7, 20] {
+            assert_matches_batch(&sonar_data, window);
+        }
+        assert_matches_batch(&EXAMPLE, 2);
+    }
+}
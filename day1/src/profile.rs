@@ -0,0 +1,379 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+/// Consecutive readings `start..=end` (0-based indices) with statistics of their depths.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+impl Span {
+    fn of(sonar_data: &[usize], start: usize, end: usize) -> Self {
+        let readings = &sonar_data[start..=end];
+
+        Self {
+            start,
+            end,
+            min: readings.iter().copied().min().unwrap_or_default(),
+            max: readings.iter().copied().max().unwrap_or_default(),
+            mean: readings.iter().sum::<usize>() as f64 / readings.len() as f64,
+        }
+    }
+
+    pub fn readings(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+/// Maximal runs of at least two readings where every reading relates to the previous one.
+fn runs(sonar_data: &[usize], related: impl Fn(usize, usize) -> bool) -> Vec<Span> {
+    let mut runs = vec![];
+    let mut start = 0;
+
+    for end in 1..=sonar_data.len() {
+        if end < sonar_data.len() && related(sonar_data[end - 1], sonar_data[end]) {
+            continue;
+        }
+
+        if end - 1 > start {
+            runs.push(Span::of(sonar_data, start, end - 1));
+        }
+        start = end;
+    }
+
+    runs
+}
+
+/// The earliest of the longest runs.
+fn longest_run(sonar_data: &[usize], related: impl Fn(usize, usize) -> bool) -> Option<Span> {
+    runs(sonar_data, related)
+        .into_iter()
+        .rev()
+        .max_by_key(Span::readings)
+}
+
+/// The earliest of the largest changes between two consecutive readings, as measured by `change`.
+fn largest_jump(sonar_data: &[usize], change: impl Fn(usize, usize) -> usize) -> Option<Span> {
+    sonar_data
+        .windows(2)
+        .enumerate()
+        .map(|(start, pair)| (start, change(pair[0], pair[1])))
+        .filter(|(_, change)| *change > 0)
+        .rev()
+        .max_by_key(|(_, change)| *change)
+        .map(|(start, _)| Span::of(sonar_data, start, start + 1))
+}
+
+/// Minimum, maximum and mean of every window of `window` readings. Minima and maxima are tracked
+/// with monotonic queues, so it takes O(n) time for any window size.
+fn rolling(sonar_data: &[usize], window: usize) -> Vec<Span> {
+    let mut minima: VecDeque<usize> = VecDeque::with_capacity(window);
+    let mut maxima: VecDeque<usize> = VecDeque::with_capacity(window);
+    let mut sum = 0;
+    let mut spans = vec![];
+
+    for (end, depth) in sonar_data.iter().copied().enumerate() {
+        while minima.back().is_some_and(|&idx| sonar_data[idx] >= depth) {
+            minima.pop_back();
+        }
+        minima.push_back(end);
+
+        while maxima.back().is_some_and(|&idx| sonar_data[idx] <= depth) {
+            maxima.pop_back();
+        }
+        maxima.push_back(end);
+
+        sum += depth;
+        if end + 1 < window {
+            continue;
+        }
+
+        let start = end + 1 - window;
+        if start > 0 {
+            sum -= sonar_data[start - 1];
+        }
+        while minima.front().is_some_and(|&idx| idx < start) {
+            minima.pop_front();
+        }
+        while maxima.front().is_some_and(|&idx| idx < start) {
+            maxima.pop_front();
+        }
+
+        spans.push(Span {
+            start,
+            end,
+            min: sonar_data[minima[0]],
+            max: sonar_data[maxima[0]],
+            mean: sum as f64 / window as f64,
+        });
+    }
+
+    spans
+}
+
+/// Shape of the sea floor along the sonar sweep. Ties are resolved in favour of the earliest span.
+#[derive(Debug, Clone)]
+pub struct DepthProfile {
+    pub readings: usize,
+    /// Longest run of readings which get strictly deeper.
+    pub longest_increase: Option<Span>,
+    /// Longest run of readings which get strictly shallower.
+    pub longest_decrease: Option<Span>,
+    /// Every run of at least two equal readings.
+    pub plateaus: Vec<Span>,
+    /// Largest increase between two consecutive readings. Only one is reported - the earliest
+    /// if several rises are equally large.
+    pub largest_rise: Option<Span>,
+    /// Largest decrease between two consecutive readings, again only the earliest one.
+    pub largest_drop: Option<Span>,
+    pub window: usize,
+    /// Statistics of every window of `window` consecutive readings.
+    pub rolling: Vec<Span>,
+}
+
+impl DepthProfile {
+    /// Panics if `window` is 0.
+    pub fn new(sonar_data: &[usize], window: usize) -> Self {
+        assert!(window > 0, "window size has to be at least 1");
+
+        Self {
+            readings: sonar_data.len(),
+            longest_increase: longest_run(sonar_data, |previous, current| current > previous),
+            longest_decrease: longest_run(sonar_data, |previous, current| current < previous),
+            plateaus: runs(sonar_data, |previous, current| current == previous),
+            largest_rise: largest_jump(sonar_data, |previous, current| {
+                current.saturating_sub(previous)
+            }),
+            largest_drop: largest_jump(sonar_data, |previous, current| {
+                previous.saturating_sub(current)
+            }),
+            window,
+            rolling: rolling(sonar_data, window),
+        }
+    }
+
+    /// One `kind,start,end,min,max,mean` row per span of the profile, rolling windows last.
+    pub fn to_csv(&self) -> String {
+        let spans = [
+            ("longest_increase", self.longest_increase.as_ref()),
+            ("longest_decrease", self.longest_decrease.as_ref()),
+            ("largest_rise", self.largest_rise.as_ref()),
+            ("largest_drop", self.largest_drop.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(kind, span)| Some((kind, span?)))
+        .chain(self.plateaus.iter().map(|span| ("plateau", span)))
+        .chain(self.rolling.iter().map(|span| ("rolling", span)));
+
+        let mut csv = String::from("kind,start,end,min,max,mean\n");
+        for (kind, span) in spans {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{:.3}\n",
+                kind, span.start, span.end, span.min, span.max, span.mean
+            ));
+        }
+
+        csv
+    }
+}
+
+impl Display for DepthProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Depth profile of {} readings", self.readings)?;
+
+        let run = |span: Option<&Span>, from: fn(&Span) -> usize, to: fn(&Span) -> usize| {
+            span.map_or("none".to_owned(), |span| {
+                format!(
+                    "{} readings at {}-{}, {} -> {}",
+                    span.readings(),
+                    span.start,
+                    span.end,
+                    from(span),
+                    to(span)
+                )
+            })
+        };
+        writeln!(
+            f,
+            "Longest increasing run: {}",
+            run(
+                self.longest_increase.as_ref(),
+                |span| span.min,
+                |span| span.max
+            )
+        )?;
+        writeln!(
+            f,
+            "Longest decreasing run: {}",
+            run(
+                self.longest_decrease.as_ref(),
+                |span| span.max,
+                |span| span.min
+            )
+        )?;
+
+        let jump = |span: Option<&Span>, from: fn(&Span) -> usize, to: fn(&Span) -> usize| {
+            span.map_or("none".to_owned(), |span| {
+                format!(
+                    "{} at {}-{}, {} -> {}",
+                    span.max - span.min,
+                    span.start,
+                    span.end,
+                    from(span),
+                    to(span)
+                )
+            })
+        };
+        writeln!(
+            f,
+            "Largest rise: {}",
+            jump(self.largest_rise.as_ref(), |span| span.min, |span| span.max)
+        )?;
+        writeln!(
+            f,
+            "Largest drop: {}",
+            jump(self.largest_drop.as_ref(), |span| span.max, |span| span.min)
+        )?;
+
+        writeln!(f, "Plateaus: {}", self.plateaus.len())?;
+        for plateau in self.plateaus.iter() {
+            writeln!(
+                f,
+                "  {} readings of {} at {}-{}",
+                plateau.readings(),
+                plateau.min,
+                plateau.start,
+                plateau.end
+            )?;
+        }
+
+        writeln!(f, "Rolling window of {} readings:", self.window)?;
+        writeln!(
+            f,
+            "{:>8} {:>8} {:>8} {:>8} {:>10}",
+            "start", "end", "min", "max", "mean"
+        )?;
+        for span in self.rolling.iter() {
+            writeln!(
+                f,
+                "{:>8} {:>8} {:>8} {:>8} {:>10.2}",
+                span.start, span.end, span.min, span.max, span.mean
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(span: Option<Span>) -> Option<(usize, usize)> {
+        span.map(|span| (span.start, span.end))
+    }
+
+    #[test]
+    fn ties_resolve_to_the_earliest_span() {
+        let profile = DepthProfile::new(&[5, 6, 7, 3, 4, 5, 9, 8, 7, 6, 2], 3);
+
+        assert_eq!(bounds(profile.longest_increase), Some((3, 6)));
+        assert_eq!(bounds(profile.longest_decrease), Some((6, 10)));
+        assert_eq!(bounds(profile.largest_rise), Some((5, 6)));
+        assert_eq!(bounds(profile.largest_drop), Some((2, 3)));
+
+        let profile = DepthProfile::new(&[1, 2, 3, 2, 1, 2, 3, 0, 3], 2);
+
+        assert_eq!(bounds(profile.longest_increase), Some((0, 2)));
+        assert_eq!(bounds(profile.longest_decrease), Some((2, 4)));
+        assert_eq!(bounds(profile.largest_rise), Some((7, 8)));
+        assert_eq!(bounds(profile.largest_drop), Some((6, 7)));
+
+        let profile = DepthProfile::new(&[1, 3, 1, 3, 1], 2);
+
+        assert_eq!(bounds(profile.largest_rise), Some((0, 1)));
+        assert_eq!(bounds(profile.largest_drop), Some((1, 2)));
+    }
+
+    #[test]
+    fn finds_every_plateau() {
+        let profile = DepthProfile::new(&[4, 4, 4, 5, 6, 6, 7, 6, 6], 3);
+
+        assert_eq!(
+            profile
+                .plateaus
+                .iter()
+                .map(|span| (span.start, span.end, span.min))
+                .collect::<Vec<_>>(),
+            [(0, 2, 4), (4, 5, 6), (7, 8, 6)]
+        );
+        assert_eq!(bounds(profile.longest_increase), Some((2, 4)));
+        assert_eq!(bounds(profile.largest_rise), Some((2, 3)));
+    }
+
+    #[test]
+    fn profiles_a_single_reading_and_no_readings() {
+        let profile = DepthProfile::new(&[7], 3);
+
+        assert_eq!(profile.readings, 1);
+        assert_eq!(profile.longest_increase, None);
+        assert_eq!(profile.longest_decrease, None);
+        assert_eq!(profile.largest_rise, None);
+        assert_eq!(profile.largest_drop, None);
+        assert!(profile.plateaus.is_empty());
+        assert!(profile.rolling.is_empty());
+
+        let profile = DepthProfile::new(&[7], 1);
+        assert_eq!(
+            profile.rolling,
+            [Span {
+                start: 0,
+                end: 0,
+                min: 7,
+                max: 7,
+                mean: 7.0
+            }]
+        );
+
+        let profile = DepthProfile::new(&[], 1);
+
+        assert_eq!(profile.readings, 0);
+        assert_eq!(profile.longest_increase, None);
+        assert_eq!(profile.largest_drop, None);
+        assert!(profile.plateaus.is_empty());
+        assert!(profile.rolling.is_empty());
+        assert_eq!(profile.to_csv(), "kind,start,end,min,max,mean\n");
+    }
+
+    #[test]
+    fn rolling_statistics_match_a_brute_force_window() {
+        let sonar_data = [
+            199, 200, 208, 210, 200, 207, 240, 269, 260, 263, 263, 150, 151, 150, 300, 1, 1, 2,
+        ];
+
+        for window in 1..=sonar_data.len() + 1 {
+            let brute_force = sonar_data
+                .windows(window)
+                .enumerate()
+                .map(|(start, readings)| Span {
+                    start,
+                    end: start + window - 1,
+                    min: *readings.iter().min().unwrap(),
+                    max: *readings.iter().max().unwrap(),
+                    mean: readings.iter().sum::<usize>() as f64 / window as f64,
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                rolling(&sonar_data, window),
+                brute_force,
+                "window {}",
+                window
+            );
+        }
+    }
+}
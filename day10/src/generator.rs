@@ -0,0 +1,78 @@
+use crate::Day10;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+const BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+fn closing(opening: char) -> char {
+    BRACKETS
+        .iter()
+        .find(|(open, _)| *open == opening)
+        .map_or(')', |(_, close)| *close)
+}
+
+/// Random walk over brackets which always stays valid. Ends with unclosed brackets left.
+fn chunks(rng: &mut StdRng, length: usize) -> (String, Vec<char>) {
+    let mut line = String::with_capacity(length);
+    let mut stack = vec![];
+
+    for _ in 0..length {
+        if stack.is_empty() || rng.gen_bool(0.55) {
+            let (open, _) = BRACKETS[rng.gen_range(0..BRACKETS.len())];
+            stack.push(open);
+            line.push(open);
+        } else if let Some(open) = stack.pop() {
+            line.push(closing(open));
+        }
+    }
+
+    if stack.is_empty() {
+        stack.push('(');
+        line.push('(');
+    }
+
+    (line, stack)
+}
+
+impl Generator for Day10 {
+    const DEFAULT_SIZE: usize = 100;
+
+    /// `size` lines, each of them either corrupted or incomplete.
+    /// The number of incomplete lines is odd, so the middle completion score is well defined.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let size = size.max(1);
+        let mut incomplete = 0;
+
+        (0..size)
+            .map(|idx| {
+                let corrupted = if idx + 1 == size {
+                    incomplete % 2 == 1
+                } else {
+                    rng.gen_bool(0.5)
+                };
+                let length = rng.gen_range(20..110);
+                let (mut line, stack) = chunks(rng, length);
+
+                if corrupted {
+                    let expected = stack.last().copied().map_or(')', closing);
+                    let illegal = BRACKETS
+                        .iter()
+                        .map(|(_, close)| *close)
+                        .filter(|close| *close != expected)
+                        .nth(rng.gen_range(0..BRACKETS.len() - 1))
+                        .unwrap_or(']');
+                    line.push(illegal);
+                    let rest = rng.gen_range(0..10);
+                    line.push_str(&chunks(rng, rest).0);
+                } else {
+                    incomplete += 1;
+                }
+
+                line.push('\n');
+                line
+            })
+            .collect::<String>()
+            .into()
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::Result;
+
+mod generator;
+mod parser;
+pub use parser::{NavigationLineParser, ParserResult};
+use utils::Solution;
+
+/// Sum of scores of the first illegal bracket in every corrupted line.
+pub fn total_syntax_error_score(navigation_subsystem: &str) -> usize {
+    navigation_subsystem
+        .lines()
+        .map(Into::<NavigationLineParser>::into)
+        .map(NavigationLineParser::parse)
+        .flat_map(|result| result.first_illegal())
+        .map(|bracket| match bracket {
+            ')' => 3,
+            ']' => 57,
+            '}' => 1197,
+            '>' => 25137,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Middle score of completions of all incomplete lines.
+pub fn total_autocompletion_score(navigation_subsystem: &str) -> usize {
+    let mut completions: Vec<usize> = navigation_subsystem
+        .lines()
+        .map(Into::<NavigationLineParser>::into)
+        .map(NavigationLineParser::parse)
+        .flat_map(ParserResult::completion)
+        .map(|completion| {
+            completion.into_iter().fold(0, |score, bracket| {
+                let bracket_score = match bracket {
+                    ')' => 1,
+                    ']' => 2,
+                    '}' => 3,
+                    '>' => 4,
+                    _ => 0,
+                };
+
+                score * 5 + bracket_score
+            })
+        })
+        .collect();
+
+    completions.sort_unstable();
+    completions[completions.len() / 2]
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(navigation_subsystem: &Self::Input) -> Result<Self::Part1> {
+        Ok(total_syntax_error_score(navigation_subsystem))
+    }
+
+    fn part2(navigation_subsystem: &Self::Input) -> Result<Self::Part2> {
+        Ok(total_autocompletion_score(navigation_subsystem))
+    }
+}
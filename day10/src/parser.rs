@@ -50,7 +50,7 @@ impl<'line> NavigationLineParser<'line> {
     const OPENING_BRACKETS: [char; 4] = ['(', '{', '<', '['];
     const CLOSING_BRACKETS: [char; 4] = [')', '}', '>', ']'];
 
-    fn new(line: &'line str) -> Self {
+    pub fn new(line: &'line str) -> Self {
         Self {
             line,
             bracket_stack: Vec::with_capacity(line.len()),
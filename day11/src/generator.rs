@@ -0,0 +1,37 @@
+use crate::{Day11, OctopusGrid};
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+/// Grids which do not synchronize within this many steps are thrown away.
+const SYNCHRONIZATION_LIMIT: usize = 10_000;
+
+impl Generator for Day11 {
+    const DEFAULT_SIZE: usize = 10;
+
+    /// `size` x `size` grid of octopuses which is known to synchronize eventually.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let size = size.max(1);
+
+        loop {
+            let input = (0..size)
+                .map(|_| {
+                    let mut row = (0..size)
+                        .map(|_| char::from_digit(rng.gen_range(0..10), 10).unwrap_or('0'))
+                        .collect::<String>();
+                    row.push('\n');
+                    row
+                })
+                .collect::<String>();
+
+            let synchronizes = input.parse::<OctopusGrid>().is_ok_and(|mut grid| {
+                let octopuses = size * size;
+                (0..SYNCHRONIZATION_LIMIT).any(|_| grid.step() == octopuses)
+            });
+
+            if synchronizes {
+                return input.into();
+            }
+        }
+    }
+}
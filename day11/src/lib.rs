@@ -0,0 +1,130 @@
+use anyhow::Result;
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+use utils::frames::{Animated, Palette, Recording};
+use utils::grid::Grid;
+use utils::Solution;
+
+mod generator;
+
+#[derive(Debug, Clone)]
+pub struct OctopusGrid {
+    board: Grid<u8>,
+}
+
+impl FromStr for OctopusGrid {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let board = Grid::parse(s, "digit", |digit| digit.to_digit(10).map(|d| d as u8))?;
+
+        Ok(Self { board })
+    }
+}
+
+impl Display for OctopusGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.board)
+    }
+}
+
+impl OctopusGrid {
+    /// Advances the simulation by one step and returns the number of octopuses which flashed.
+    pub fn step(&mut self) -> usize {
+        let mut step_flashes = 0;
+
+        for octopus in self.board.iter_mut() {
+            *octopus += 1;
+        }
+
+        let mut flash_positions = self
+            .board
+            .positions()
+            .filter(|&position| self.board[position] >= 10)
+            .collect::<Vec<_>>();
+
+        let mut already_flashed = HashSet::with_capacity(self.board.len());
+        already_flashed.extend(flash_positions.iter().copied());
+
+        while let Some((x, y)) = flash_positions.pop() {
+            step_flashes += 1;
+
+            for neighbour in self.board.neighbours8(x, y) {
+                self.board[neighbour] += 1;
+                if self.board[neighbour] >= 10 && already_flashed.insert(neighbour) {
+                    flash_positions.push(neighbour);
+                }
+            }
+        }
+
+        already_flashed
+            .into_iter()
+            .for_each(|position| self.board[position] = 0);
+
+        step_flashes
+    }
+
+    /// Advances the simulation until all octopuses flash at once and returns the number of steps it took.
+    pub fn synchronized_step(&mut self) -> usize {
+        let octopuses = self.board.len();
+
+        std::iter::repeat(())
+            .take_while(|_| self.step() != octopuses)
+            .count()
+            + 1
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = OctopusGrid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(cave: &Self::Input) -> Result<Self::Part1> {
+        let mut cave = cave.clone();
+
+        Ok((0..100).map(|_| cave.step()).sum())
+    }
+
+    fn part2(cave: &Self::Input) -> Result<Self::Part2> {
+        Ok(cave.clone().synchronized_step())
+    }
+}
+
+impl Animated for Day11 {
+    /// Energy levels from dark blue to light blue, flashing octopuses (energy 0) are white.
+    fn palette() -> Palette {
+        Palette::new(
+            (0..10)
+                .map(|level| match level {
+                    0 => [255, 255, 255],
+                    level => [10 * level, 10 * level, 60 + 15 * level],
+                })
+                .collect(),
+        )
+    }
+
+    /// Runs until all octopuses flash at once if `steps` are not given.
+    fn record(cave: &Self::Input, steps: Option<usize>) -> Result<Recording> {
+        let mut cave = cave.clone();
+        let octopuses = cave.board.len();
+        let mut recording = Recording::new();
+        recording.capture(&cave.board, |energy| *energy);
+
+        for _ in 0..steps.unwrap_or(usize::MAX) {
+            let flashes = cave.step();
+            recording.capture(&cave.board, |energy| *energy);
+
+            if steps.is_none() && flashes == octopuses {
+                break;
+            }
+        }
+
+        Ok(recording)
+    }
+}
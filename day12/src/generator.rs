@@ -0,0 +1,91 @@
+use crate::Day12;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use utils::generator::{GeneratedInput, Generator};
+
+fn cave_names(rng: &mut StdRng, count: usize, letters: &[u8]) -> Vec<String> {
+    let mut names = HashSet::new();
+
+    while names.len() < count {
+        let name = (0..2)
+            .map(|_| letters[rng.gen_range(0..letters.len())] as char)
+            .collect::<String>();
+        names.insert(name);
+    }
+
+    let mut names = names.into_iter().collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+impl Generator for Day12 {
+    const DEFAULT_SIZE: usize = 22;
+
+    /// Cave system with about `size` connections. Two big caves are never connected
+    /// directly - otherwise there would be infinitely many paths.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let lowercase = (b'a'..=b'z').collect::<Vec<_>>();
+        let uppercase = (b'A'..=b'Z').collect::<Vec<_>>();
+
+        let small = cave_names(rng, (size / 4).max(2), &lowercase);
+        let big = cave_names(rng, (size / 10).max(1), &uppercase);
+        let is_big = |cave: &String| big.contains(cave);
+
+        // Big caves go into distinct gaps between small ones, so that a chain
+        // through all the caves never links two big caves.
+        let mut caves = small.clone();
+        caves.shuffle(rng);
+        let mut gaps = (0..=caves.len()).collect::<Vec<_>>();
+        gaps.shuffle(rng);
+        let mut gaps = gaps[..big.len()].to_vec();
+        gaps.sort_unstable();
+        for (cave, gap) in big.iter().zip(gaps).rev() {
+            caves.insert(gap, cave.clone());
+        }
+
+        let chain = std::iter::once("start".to_owned())
+            .chain(caves.iter().cloned())
+            .chain(std::iter::once("end".to_owned()))
+            .collect::<Vec<_>>();
+        let mut connections = chain
+            .windows(2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect::<Vec<_>>();
+        let connected = |connections: &[(String, String)], a: &String, b: &String| {
+            connections
+                .iter()
+                .any(|(x, y)| (x == a && y == b) || (x == b && y == a))
+        };
+
+        let mut attempts = 0;
+        while connections.len() < size && attempts < size * 100 {
+            attempts += 1;
+
+            let a = caves[rng.gen_range(0..caves.len())].clone();
+            let b = match rng.gen_range(0..10) {
+                0 => "start".to_owned(),
+                1 => "end".to_owned(),
+                _ => caves[rng.gen_range(0..caves.len())].clone(),
+            };
+
+            if a == b || (is_big(&a) && is_big(&b)) || connected(&connections, &a, &b) {
+                continue;
+            }
+            connections.push((a, b));
+        }
+
+        connections
+            .into_iter()
+            .map(|(a, b)| {
+                if rng.gen() {
+                    format!("{}-{}\n", a, b)
+                } else {
+                    format!("{}-{}\n", b, a)
+                }
+            })
+            .collect::<String>()
+            .into()
+    }
+}
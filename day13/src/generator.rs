@@ -0,0 +1,68 @@
+use crate::Day13;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::collections::HashSet;
+use utils::generator::{GeneratedInput, Generator};
+
+/// Size of the page after all folds - room for eight capital letters.
+const FOLDED_PAGE: (usize, usize) = (40, 6);
+
+impl Generator for Day13 {
+    const DEFAULT_SIZE: usize = 12;
+
+    /// Page folded `size` times. It is built backwards: random dots on the folded page are
+    /// unfolded one fold at a time, landing on either or both halves - never on the fold line.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let (mut width, mut height) = FOLDED_PAGE;
+        let mut dots = (0..width)
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .filter(|_| rng.gen_bool(0.4))
+            .collect::<HashSet<_>>();
+        let mut folds = vec![];
+
+        for _ in 0..size {
+            let along_x = rng.gen();
+            let line = if along_x { width } else { height };
+
+            dots = dots
+                .into_iter()
+                .flat_map(|(x, y)| {
+                    let mirrored = if along_x {
+                        (2 * line - x, y)
+                    } else {
+                        (x, 2 * line - y)
+                    };
+
+                    match rng.gen_range(0..3) {
+                        0 => vec![(x, y)],
+                        1 => vec![mirrored],
+                        _ => vec![(x, y), mirrored],
+                    }
+                })
+                .collect();
+
+            if along_x {
+                width = 2 * width + 1;
+                folds.push(format!("fold along x={}", line));
+            } else {
+                height = 2 * height + 1;
+                folds.push(format!("fold along y={}", line));
+            }
+        }
+
+        let mut dots = dots.into_iter().collect::<Vec<_>>();
+        dots.sort_unstable_by_key(|(x, y)| (*y, *x));
+
+        let dots = dots
+            .into_iter()
+            .map(|(x, y)| format!("{},{}\n", x, y))
+            .collect::<String>();
+        let folds = folds
+            .into_iter()
+            .rev()
+            .map(|fold| format!("{}\n", fold))
+            .collect::<String>();
+
+        format!("{}\n{}", dots, folds).into()
+    }
+}
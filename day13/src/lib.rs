@@ -0,0 +1,174 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
+use utils::grid::Grid;
+use utils::parser::{
+    blank_line, integer, map, one_of, parse_all, preceded, section, separated_pair, tag, Parser,
+};
+use utils::Solution;
+
+mod generator;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageFold {
+    FoldY(usize),
+    FoldX(usize),
+}
+
+#[derive(Debug)]
+pub struct ManualPage {
+    dots: Vec<(usize, usize)>,
+    folds: Vec<PageFold>,
+}
+
+fn page_fold<'a>() -> impl Parser<'a, PageFold> {
+    let axis = one_of([
+        ("x", PageFold::FoldX as fn(usize) -> PageFold),
+        ("y", PageFold::FoldY),
+    ]);
+
+    map(
+        preceded(
+            tag("fold along "),
+            separated_pair(axis, tag("="), integer()),
+        ),
+        |(fold, point)| fold(point),
+    )
+}
+
+fn dot<'a>() -> impl Parser<'a, (usize, usize)> {
+    separated_pair(integer(), tag(","), integer())
+}
+
+impl FromStr for PageFold {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(s, page_fold())?)
+    }
+}
+
+struct DotMap(HashSet<(usize, usize)>);
+
+impl Display for DotMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "<empty result>");
+        }
+
+        let max_x = self
+            .0
+            .iter()
+            .max_by_key(|point| point.0)
+            .map(|point| point.0);
+
+        let max_y = self
+            .0
+            .iter()
+            .max_by_key(|point| point.1)
+            .map(|point| point.1);
+
+        // SAFETY: There is a short-circuit check at the beginning of this function for an empty point cloud.
+        let (mx, my) = max_x.zip(max_y).unwrap();
+
+        let mut board = Grid::new(mx + 1, my + 1, '.');
+
+        self.0.iter().copied().for_each(|position| {
+            board[position] = '#';
+        });
+
+        write!(f, "{}", board)
+    }
+}
+
+impl FromStr for ManualPage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dots, folds) = parse_all(
+            s,
+            separated_pair(section(dot()), blank_line(), section(page_fold())),
+        )?;
+
+        Ok(Self { folds, dots })
+    }
+}
+
+impl PageFold {
+    /// Position of the dot after folding the page along this line.
+    pub fn translate_dot(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        use PageFold::*;
+
+        match self {
+            FoldX(axis_start) => {
+                let mut x = x;
+                if x > *axis_start {
+                    x = *axis_start - (x - *axis_start);
+                }
+
+                (x, y)
+            }
+            FoldY(axis_start) => {
+                let mut y = y;
+                if y > *axis_start {
+                    y = *axis_start - (y - *axis_start);
+                }
+
+                (x, y)
+            }
+        }
+    }
+}
+
+impl ManualPage {
+    pub fn dots(&self) -> &[(usize, usize)] {
+        &self.dots
+    }
+
+    pub fn folds(&self) -> &[PageFold] {
+        &self.folds
+    }
+
+    /// Dot positions after the first `folds_count` folds, or all of them. Overlapping dots are kept.
+    pub fn final_dots(&self, folds_count: Option<usize>) -> Vec<(usize, usize)> {
+        self.dots
+            .iter()
+            .copied()
+            .map(|dot| {
+                let folds = self.folds.iter().take(folds_count.unwrap_or(usize::MAX));
+
+                folds.fold(dot, |dot, fold| fold.translate_dot(dot))
+            })
+            .collect()
+    }
+
+    /// Number of distinct dots visible after the first `folds_count` folds, or all of them.
+    pub fn count_dots(&self, folds_count: Option<usize>) -> usize {
+        HashSet::<(usize, usize)>::from_iter(self.final_dots(folds_count)).len()
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = ManualPage;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(manual_page: &Self::Input) -> Result<Self::Part1> {
+        Ok(manual_page.count_dots(Some(1)))
+    }
+
+    fn part2(manual_page: &Self::Input) -> Result<Self::Part2> {
+        let folded_dots_map = DotMap(HashSet::<(usize, usize)>::from_iter(
+            manual_page.final_dots(None),
+        ));
+
+        Ok(folded_dots_map.to_string())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::Day14;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+const ELEMENTS: usize = 10;
+
+impl Generator for Day14 {
+    const DEFAULT_SIZE: usize = 20;
+
+    /// Template of `size` elements and an insertion rule for every pair of the ten elements used.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let alphabet = ('A'..='Z').collect::<Vec<_>>();
+        let elements = alphabet
+            .choose_multiple(rng, ELEMENTS)
+            .copied()
+            .collect::<Vec<_>>();
+        let element = |rng: &mut StdRng| elements[rng.gen_range(0..elements.len())];
+
+        let template = (0..size.max(2)).map(|_| element(rng)).collect::<String>();
+
+        let rules = elements
+            .iter()
+            .flat_map(|a| elements.iter().map(move |b| (*a, *b)))
+            .map(|(a, b)| format!("{}{} -> {}\n", a, b, element(rng)))
+            .collect::<String>();
+
+        format!("{}\n\n{}", template, rules).into()
+    }
+}
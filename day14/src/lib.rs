@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Result};
+use std::{collections::HashMap, str::FromStr};
+use utils::windows::WindowExt;
+use utils::Solution;
+
+mod generator;
+
+#[derive(Debug)]
+struct PairRule {
+    pair: (char, char),
+    product: char,
+}
+
+impl FromStr for PairRule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut pair_product_split = s.split(" -> ");
+        let pair = pair_product_split
+            .next()
+            .ok_or(anyhow!("failed to find pair for pair rule: {}", s))?;
+        let product = pair_product_split
+            .next()
+            .ok_or(anyhow!("failed to find product for pair rule: {}", s))?;
+
+        let product_elements = product.chars().collect::<Vec<_>>();
+        let pair_elements = pair.chars().collect::<Vec<_>>();
+
+        Ok(Self {
+            pair: (
+                pair_elements.first().copied().ok_or(anyhow!(
+                    "failed to find first element of pair in pair rule: {}",
+                    s
+                ))?,
+                pair_elements.get(1).copied().ok_or(anyhow!(
+                    "failed to find second element of pair in pair rule: {}",
+                    s
+                ))?,
+            ),
+            product: product_elements.first()
+                .copied()
+                .ok_or(anyhow!("failed to find product in pair rule: {}", s))?,
+        })
+    }
+}
+
+pub type Ruleset = HashMap<(char, char), char>;
+
+fn read_input(data: &str) -> Result<(Vec<char>, Ruleset)> {
+    let mut lines = data.lines();
+
+    let first_line = lines
+        .next()
+        .ok_or_else(|| anyhow!("data malformed - first line doesn't exist"))?;
+
+    let chain = first_line.chars().collect();
+    let mut ruleset = HashMap::new();
+
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+
+        let pair_rule: PairRule = line.parse()?;
+        ruleset.insert(pair_rule.pair, pair_rule.product);
+    }
+
+    Ok((chain, ruleset))
+}
+
+/// Difference between the quantities of the most and the least common element after `steps` steps.
+pub fn simulate_polymerisation(
+    starting_polymer: impl IntoIterator<Item = char>,
+    ruleset: &HashMap<(char, char), char>,
+    steps: usize,
+) -> usize {
+    let mut elements_counter: HashMap<char, usize> = HashMap::new();
+    let mut producing_pairs: HashMap<(char, char), usize> = HashMap::with_capacity(ruleset.len());
+
+    let starting_polymer = starting_polymer.into_iter().inspect(|element| {
+        *elements_counter.entry(*element).or_default() += 1;
+    });
+
+    for [substrate_a, substrate_b] in starting_polymer.windows::<2>() {
+        let pair = (substrate_a, substrate_b);
+        if ruleset.contains_key(&pair) {
+            *producing_pairs.entry(pair).or_default() += 1;
+        }
+    }
+
+    for _ in 0..steps {
+        let mut new_producing_pairs = HashMap::with_capacity(ruleset.len());
+        for (pair, count) in producing_pairs.into_iter() {
+            let product = ruleset[&pair];
+            let (substrate_a, substrate_b) = pair;
+            let result_first = (substrate_a, product);
+            let result_second = (product, substrate_b);
+
+            *elements_counter.entry(product).or_default() += count;
+
+            if ruleset.contains_key(&result_first) {
+                *new_producing_pairs.entry(result_first).or_default() += count;
+            }
+
+            if ruleset.contains_key(&result_second) {
+                *new_producing_pairs.entry(result_second).or_default() += count;
+            }
+        }
+        producing_pairs = new_producing_pairs;
+    }
+
+    let most_occuring_element = elements_counter
+        .iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(_, count)| count)
+        .copied();
+    let least_occuring_element = elements_counter
+        .iter()
+        .min_by_key(|(_, count)| *count)
+        .map(|(_, count)| count)
+        .copied();
+
+    if let Some((max, min)) = most_occuring_element.zip(least_occuring_element) {
+        max - min
+    } else {
+        panic!("Invalid analysis - empty chain");
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = (Vec<char>, Ruleset);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input)
+    }
+
+    fn part1((starting_polymer, ruleset): &Self::Input) -> Result<Self::Part1> {
+        Ok(simulate_polymerisation(
+            starting_polymer.iter().copied(),
+            ruleset,
+            10,
+        ))
+    }
+
+    fn part2((starting_polymer, ruleset): &Self::Input) -> Result<Self::Part2> {
+        Ok(simulate_polymerisation(
+            starting_polymer.iter().copied(),
+            ruleset,
+            40,
+        ))
+    }
+}
@@ -0,0 +1,24 @@
+use crate::Day15;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day15 {
+    const DEFAULT_SIZE: usize = 100;
+
+    /// `size` x `size` map of risk levels between 1 and 9.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let size = size.max(1);
+
+        (0..size)
+            .map(|_| {
+                let mut row = (0..size)
+                    .map(|_| char::from_digit(rng.gen_range(1..=9), 10).unwrap_or('9'))
+                    .collect::<String>();
+                row.push('\n');
+                row
+            })
+            .collect::<String>()
+            .into()
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::{anyhow, Result};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use utils::grid::Grid;
+use utils::search::{astar, SearchProblem};
+use utils::Solution;
+
+mod generator;
+
+pub struct CaveMap {
+    data: Grid<usize>,
+}
+
+impl Display for CaveMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.data)
+    }
+}
+
+impl FromStr for CaveMap {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = Grid::parse(s, "risk level digit 1-9", |risk_level| {
+            risk_level
+                .to_digit(10)
+                .filter(|&level| level > 0)
+                .map(|level| level as usize)
+        })?;
+
+        Ok(Self { data })
+    }
+}
+
+impl CaveMap {
+    /// Risk levels of the scanned part of the cave.
+    pub fn scanned(&self) -> &Grid<usize> {
+        &self.data
+    }
+
+    /// The whole cave is the scanned map tiled 5 times in both directions, with the risk level
+    /// increased by the tile distance from the top left corner and wrapping back to 1 after 9.
+    pub fn full_cave(&self) -> Grid<usize> {
+        let (width, height) = (self.data.width(), self.data.height());
+
+        Grid::from_fn(width * 5, height * 5, |x, y| {
+            let raw_cost = self.data[(x % width, y % height)];
+            (raw_cost + x / width + y / height - 1) % 9 + 1
+        })
+    }
+}
+
+/// Route from the top left corner to the bottom right one, entering a position costs its risk level.
+struct Route<'a> {
+    cave: &'a Grid<usize>,
+}
+
+impl Route<'_> {
+    fn exit(&self) -> (usize, usize) {
+        (self.cave.width() - 1, self.cave.height() - 1)
+    }
+}
+
+impl SearchProblem for Route<'_> {
+    type State = (usize, usize);
+    type Cost = usize;
+
+    fn start(&self) -> Self::State {
+        (0, 0)
+    }
+
+    fn is_goal(&self, position: &Self::State) -> bool {
+        *position == self.exit()
+    }
+
+    fn successors(&self, &(x, y): &Self::State) -> impl Iterator<Item = (Self::State, usize)> {
+        self.cave
+            .neighbours4(x, y)
+            .map(|position| (position, self.cave[position]))
+    }
+
+    /// Every risk level is at least 1, so the exit is at least as risky as it is far away.
+    fn heuristic(&self, &(x, y): &Self::State) -> usize {
+        let (exit_x, exit_y) = self.exit();
+        (exit_x - x) + (exit_y - y)
+    }
+}
+
+/// Total risk of the safest route from the top left corner to the bottom right one.
+pub fn lowest_risk_level(cave: &Grid<usize>) -> Result<usize> {
+    astar(&Route { cave })
+        .map(|path| path.cost)
+        .ok_or_else(|| anyhow!("there is no route to the exit"))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = CaveMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(cave_map: &Self::Input) -> Result<Self::Part1> {
+        lowest_risk_level(&cave_map.data)
+    }
+
+    fn part2(cave_map: &Self::Input) -> Result<Self::Part2> {
+        lowest_risk_level(&cave_map.full_cave())
+    }
+}
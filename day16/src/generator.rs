@@ -0,0 +1,105 @@
+use crate::Day16;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+const LITERAL: u8 = 4;
+const PRODUCT: u8 = 1;
+
+enum Packet {
+    Literal(u8, usize),
+    Operator(u8, u8, Vec<Packet>),
+}
+
+fn literal(rng: &mut StdRng) -> Packet {
+    Packet::Literal(rng.gen_range(0..8), rng.gen_range(0..1 << 16))
+}
+
+/// Random packet tree no deeper than `depth`. Products only multiply literals and the number
+/// of subpackets is limited, so the value of the transmission always fits in `usize`.
+fn packet(rng: &mut StdRng, depth: usize) -> Packet {
+    if depth == 0 || rng.gen_bool(0.3) {
+        return literal(rng);
+    }
+
+    let type_id = match rng.gen_range(0..7) {
+        id if id >= LITERAL => id + 1,
+        id => id,
+    };
+    let subpackets = match type_id {
+        PRODUCT => (0..rng.gen_range(1..=3)).map(|_| literal(rng)).collect(),
+        5..=7 => (0..2).map(|_| packet(rng, depth - 1)).collect(),
+        _ => (0..rng.gen_range(1..=4))
+            .map(|_| packet(rng, depth - 1))
+            .collect(),
+    };
+
+    Packet::Operator(rng.gen_range(0..8), type_id, subpackets)
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: usize, width: usize) {
+    bits.extend((0..width).rev().map(|bit| value >> bit & 1 == 1));
+}
+
+fn encode(rng: &mut StdRng, packet: &Packet, bits: &mut Vec<bool>) {
+    match packet {
+        Packet::Literal(version, value) => {
+            push_bits(bits, *version as usize, 3);
+            push_bits(bits, LITERAL as usize, 3);
+
+            let groups = (usize::BITS - value.leading_zeros()).max(1).div_ceil(4) as usize;
+            for group in (0..groups).rev() {
+                bits.push(group > 0);
+                push_bits(bits, value >> (group * 4) & 0xF, 4);
+            }
+        }
+        Packet::Operator(version, type_id, subpackets) => {
+            push_bits(bits, *version as usize, 3);
+            push_bits(bits, *type_id as usize, 3);
+
+            let mut payload = vec![];
+            for subpacket in subpackets {
+                encode(rng, subpacket, &mut payload);
+            }
+
+            if rng.gen() {
+                bits.push(false);
+                push_bits(bits, payload.len(), 15);
+            } else {
+                bits.push(true);
+                push_bits(bits, subpackets.len(), 11);
+            }
+            bits.extend(payload);
+        }
+    }
+}
+
+impl Generator for Day16 {
+    const DEFAULT_SIZE: usize = 6;
+
+    /// Transmission with packets nested up to `size` levels deep, written as hexadecimal.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let packet = packet(rng, size);
+        let mut bits = vec![];
+        encode(rng, &packet, &mut bits);
+
+        while bits.len() % 4 != 0 {
+            bits.push(false);
+        }
+
+        let mut hex = bits
+            .chunks(4)
+            .map(|nibble| {
+                let value = nibble
+                    .iter()
+                    .fold(0, |value, bit| value << 1 | u32::from(*bit));
+                char::from_digit(value, 16)
+                    .unwrap_or('0')
+                    .to_ascii_uppercase()
+            })
+            .collect::<String>();
+        hex.push('\n');
+
+        hex.into()
+    }
+}
@@ -0,0 +1,285 @@
+use anyhow::{anyhow, Result};
+use std::str::{Chars, FromStr};
+use utils::Solution;
+
+mod generator;
+
+/// Packet of the Buoyancy Interchange Transmission System, decoded from its hexadecimal form.
+#[derive(Debug)]
+pub struct BITSPacket {
+    version: u8,
+    payload: Payload,
+}
+
+impl BITSPacket {
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    /// Sum of versions of this packet and all its subpackets.
+    pub fn version_sum(&self) -> usize {
+        let main_version = self.version as usize;
+        match &self.payload {
+            Payload::LiteralValue(_) => main_version,
+            Payload::OperatorPayload(_, subpackets) => {
+                main_version
+                    + subpackets
+                        .iter()
+                        .map(BITSPacket::version_sum)
+                        .sum::<usize>()
+            }
+        }
+    }
+
+    fn parse(iter: &mut BitsIter) -> anyhow::Result<Self> {
+        let version = Self::read_to_u8(iter, 3);
+        let type_id = Self::read_to_u8(iter, 3);
+
+        match type_id {
+            4 => {
+                let literal_value = Self::read_literal(iter);
+                Ok(Self {
+                    version,
+                    payload: Payload::LiteralValue(literal_value),
+                })
+            }
+            operator_id => {
+                let length_type_id = Self::read_to_u8(iter, 1);
+
+                match length_type_id {
+                    0 => {
+                        let total_bits_len = Self::read_to_u16(iter, 15) as usize;
+                        let mut processed_bits_len = 0;
+                        let mut subpackets = vec![];
+
+                        while processed_bits_len != total_bits_len {
+                            let processed_before = iter.processed_bits();
+                            subpackets.push(Self::parse(iter)?);
+                            processed_bits_len += iter.processed_bits() - processed_before;
+                        }
+
+                        Ok(Self {
+                            version,
+                            payload: Payload::OperatorPayload(
+                                operator_type(operator_id)?,
+                                subpackets,
+                            ),
+                        })
+                    }
+                    1 => {
+                        let subpackets_count = Self::read_to_u16(iter, 11) as usize;
+
+                        Ok(Self {
+                            version,
+                            payload: Payload::OperatorPayload(
+                                operator_type(operator_id)?,
+                                (0..subpackets_count)
+                                    .map(|_| Self::parse(iter))
+                                    .collect::<Result<_, _>>()?,
+                            ),
+                        })
+                    }
+                    _ => Err(anyhow!("Unknown length type ID - {}", length_type_id)),
+                }
+            }
+        }
+    }
+
+    fn read_to_u8(iter: &mut BitsIter, len: usize) -> u8 {
+        let mut result = 0;
+
+        for (i, bit) in iter.take(len).enumerate() {
+            if bit == 1 {
+                result |= 1 << (len - i - 1);
+            }
+        }
+
+        result
+    }
+
+    fn read_to_u16(iter: &mut BitsIter, len: usize) -> u16 {
+        let mut result = 0;
+
+        for (i, bit) in iter.take(len).enumerate() {
+            if bit == 1 {
+                result |= 1 << (len - i - 1);
+            }
+        }
+
+        result
+    }
+
+    fn read_literal(iter: &mut BitsIter) -> usize {
+        let mut bytes = Vec::with_capacity(8);
+
+        loop {
+            let piece = Self::read_to_u16(iter, 5);
+            let value = piece & 0x0F;
+            let continue_bit = piece >> 4;
+
+            bytes.push(value);
+            if continue_bit == 0 {
+                break;
+            }
+        }
+
+        let total_nibbles = bytes.len();
+
+        bytes
+            .into_iter()
+            .enumerate()
+            .fold(0usize, |total, (i, byte)| {
+                total | ((byte as usize) << ((total_nibbles - 1 - i) * 4))
+            })
+    }
+
+    /// Value of the expression the packet represents.
+    pub fn evaluate(&self) -> anyhow::Result<usize> {
+        use OperatorType::*;
+        match &self.payload {
+            Payload::LiteralValue(value) => Ok(*value),
+            Payload::OperatorPayload(operator, data) => {
+                let mut evaluated_payload = data.iter().flat_map(BITSPacket::evaluate);
+
+                match *operator {
+                    Sum => Ok(evaluated_payload.sum()),
+                    Product => Ok(evaluated_payload.product()),
+                    Maximum => evaluated_payload
+                        .max()
+                        .ok_or(anyhow!("failed to find maximum in payload")),
+                    Minimum => evaluated_payload
+                        .min()
+                        .ok_or(anyhow!("failed to find minimum in payload")),
+                    GreaterThan => evaluated_payload
+                        .next()
+                        .zip(evaluated_payload.next())
+                        .map(|(x, y)| usize::from(x > y))
+                        .ok_or(anyhow!("failed to find two values to compare in payload")),
+                    LessThan => evaluated_payload
+                        .next()
+                        .zip(evaluated_payload.next())
+                        .map(|(x, y)| usize::from(x < y))
+                        .ok_or(anyhow!("failed to find two values to compare in payload")),
+                    EqualTo => evaluated_payload
+                        .next()
+                        .zip(evaluated_payload.next())
+                        .map(|(x, y)| usize::from(x == y))
+                        .ok_or(anyhow!("failed to find two values to compare in payload")),
+                }
+            }
+        }
+    }
+}
+
+impl<'iter> TryFrom<BitsIter<'iter>> for BITSPacket {
+    type Error = anyhow::Error;
+
+    fn try_from(mut iter: BitsIter<'iter>) -> Result<Self, Self::Error> {
+        BITSPacket::parse(&mut iter)
+    }
+}
+
+impl FromStr for BITSPacket {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BITSPacket::try_from(BitsIter::take(s.trim()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorType {
+    Sum,
+    Product,
+    Maximum,
+    Minimum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+fn operator_type(operation_id: u8) -> anyhow::Result<OperatorType> {
+    use OperatorType::*;
+
+    match operation_id {
+        0 => Ok(Sum),
+        1 => Ok(Product),
+        2 => Ok(Minimum),
+        3 => Ok(Maximum),
+        5 => Ok(GreaterThan),
+        6 => Ok(LessThan),
+        7 => Ok(EqualTo),
+        _ => Err(anyhow!("unknown operation id: {}", operation_id)),
+    }
+}
+
+#[derive(Debug)]
+pub enum Payload {
+    LiteralValue(usize),
+    OperatorPayload(OperatorType, Vec<BITSPacket>),
+}
+
+#[derive(Clone)]
+struct BitsIter<'packet> {
+    chars: Chars<'packet>,
+    bit_idx: u8,
+    current: u8,
+    processed_bits: usize,
+}
+
+impl<'packet> BitsIter<'packet> {
+    fn take(s: &'packet str) -> Self {
+        Self {
+            chars: s.chars(),
+            bit_idx: 4,
+            current: 0,
+            processed_bits: 0,
+        }
+    }
+
+    fn processed_bits(&self) -> usize {
+        self.processed_bits
+    }
+}
+
+impl<'packet> Iterator for BitsIter<'packet> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bit_idx == 4 {
+            self.current = self.chars.next().and_then(|ch| ch.to_digit(16))? as u8;
+            self.bit_idx = 0;
+        }
+
+        let bit = self.current >> (3 - self.bit_idx) & 1;
+        self.bit_idx += 1;
+        self.processed_bits += 1;
+        Some(bit)
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = BITSPacket;
+    type Part1 = usize;
+    type Part2 = usize;
+    // Comparison operators used to always evaluate to 0.
+    const VERSION: u32 = 2;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(packet: &Self::Input) -> Result<Self::Part1> {
+        Ok(packet.version_sum())
+    }
+
+    fn part2(packet: &Self::Input) -> Result<Self::Part2> {
+        packet.evaluate()
+    }
+}
@@ -0,0 +1,27 @@
+use crate::Day17;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day17 {
+    const DEFAULT_SIZE: usize = 1;
+
+    /// Target area ahead of and below the launcher. Its x range contains a triangular number,
+    /// so some probes stop moving forward right above it - the highest shots rely on that.
+    /// The input is a single line, so `size` is ignored.
+    fn generate(rng: &mut StdRng, _: usize) -> GeneratedInput {
+        let steps = rng.gen_range(6..25);
+        let triangular: isize = steps * (steps + 1) / 2;
+
+        let x_start = triangular - rng.gen_range(0..=steps.min(10));
+        let x_end = triangular + rng.gen_range(5..40);
+        let y_start = -rng.gen_range(20..200);
+        let y_end = y_start + rng.gen_range(5..20);
+
+        format!(
+            "target area: x={}..{}, y={}..{}\n",
+            x_start, x_end, y_start, y_end
+        )
+        .into()
+    }
+}
@@ -0,0 +1,27 @@
+use crate::Day18;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+/// Numbers in the homework are already reduced - no pair is nested inside four pairs.
+const MAX_DEPTH: usize = 4;
+
+fn number(rng: &mut StdRng, depth: usize) -> String {
+    if depth > 0 && (depth == MAX_DEPTH || rng.gen_bool(0.3)) {
+        rng.gen_range(0..10).to_string()
+    } else {
+        format!("[{},{}]", number(rng, depth + 1), number(rng, depth + 1))
+    }
+}
+
+impl Generator for Day18 {
+    const DEFAULT_SIZE: usize = 100;
+
+    /// Homework of `size` snailfish numbers.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        (0..size.max(1))
+            .map(|_| format!("{}\n", number(rng, 0)))
+            .collect::<String>()
+            .into()
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{Day19, ROTATIONS};
+use nalgebra as na;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt::Write;
+use utils::generator::{GeneratedInput, Generator};
+
+/// Scanners detect beacons at most this far away along every axis.
+const RANGE: i64 = 1000;
+/// Beacons seen by a scanner and the one it is placed next to. The solver needs 12 of them,
+/// the rest is a margin for beacons whose distance fingerprints happen to collide.
+const SHARED_BEACONS: usize = 14;
+/// Smallest distance between two scanners along the axis they are furthest apart on.
+const MIN_SEPARATION: i64 = 900;
+
+fn point_in(rng: &mut StdRng, min: &na::Point3<i64>, max: &na::Point3<i64>) -> na::Point3<i64> {
+    na::Point3::new(
+        rng.gen_range(min.x..=max.x),
+        rng.gen_range(min.y..=max.y),
+        rng.gen_range(min.z..=max.z),
+    )
+}
+
+fn sees(scanner: &na::Point3<i64>, beacon: &na::Point3<i64>) -> bool {
+    (beacon - scanner).amax() <= RANGE
+}
+
+impl Generator for Day19 {
+    const DEFAULT_SIZE: usize = 30;
+
+    /// Report of `size` scanners. Every scanner after the first is placed near one of the
+    /// previous ones and shares enough beacons with it to be located. Ground truth lists
+    /// the scanner poses relative to scanner 0 and both answers.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let size = size.max(1);
+        let range = na::Vector3::new(RANGE, RANGE, RANGE);
+        let mut positions = vec![na::Point3::origin()];
+        let mut rotations = vec![ROTATIONS[0]];
+        let mut beacons = HashSet::new();
+
+        for idx in 0..size {
+            if idx > 0 {
+                // Scanners are kept apart, so that every one of them sees only a few dozen beacons.
+                let (neighbour, position) = loop {
+                    let neighbour = positions[rng.gen_range(0..idx)];
+                    let offset = na::Vector3::new(
+                        rng.gen_range(-1200..=1200),
+                        rng.gen_range(-1200..=1200),
+                        rng.gen_range(-1200..=1200),
+                    );
+                    let position = neighbour + offset;
+
+                    if positions
+                        .iter()
+                        .all(|other| (position - other).amax() >= MIN_SEPARATION)
+                    {
+                        break (neighbour, position);
+                    }
+                };
+
+                // Intersection of both scanners' ranges.
+                let min = (neighbour - range).sup(&(position - range));
+                let max = (neighbour + range).inf(&(position + range));
+                let shared_before = beacons.len();
+                while beacons.len() < shared_before + SHARED_BEACONS {
+                    beacons.insert(point_in(rng, &min, &max));
+                }
+
+                positions.push(position);
+                rotations.push(ROTATIONS[rng.gen_range(0..ROTATIONS.len())]);
+            }
+
+            let position = positions[idx];
+            for _ in 0..rng.gen_range(5..15) {
+                beacons.insert(point_in(rng, &(position - range), &(position + range)));
+            }
+        }
+
+        let mut input = String::new();
+        for (id, (position, rotation)) in positions.iter().zip(rotations.iter()).enumerate() {
+            if id > 0 {
+                input.push('\n');
+            }
+            let _ = writeln!(input, "--- scanner {} ---", id);
+
+            let mut seen = beacons
+                .iter()
+                .filter(|beacon| sees(position, beacon))
+                .map(|beacon| rotation.transpose() * (beacon - position))
+                .collect::<Vec<_>>();
+            seen.sort_unstable_by_key(|beacon| (beacon.x, beacon.y, beacon.z));
+
+            for beacon in seen {
+                let _ = writeln!(input, "{},{},{}", beacon.x, beacon.y, beacon.z);
+            }
+        }
+
+        let mut ground_truth = String::new();
+        for (id, (position, rotation)) in positions.iter().zip(rotations.iter()).enumerate() {
+            let _ = writeln!(
+                ground_truth,
+                "scanner {} at {},{},{} rotated by {:?}",
+                id,
+                position.x,
+                position.y,
+                position.z,
+                rotation
+                    .row_iter()
+                    .map(|row| [row[0], row[1], row[2]])
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        let beacons_seen = beacons
+            .iter()
+            .filter(|beacon| positions.iter().any(|position| sees(position, beacon)))
+            .count();
+        let largest_distance = positions
+            .iter()
+            .flat_map(|a| positions.iter().map(move |b| (a - b).abs().sum()))
+            .max()
+            .unwrap_or_default();
+        let _ = writeln!(ground_truth, "part 1: {}", beacons_seen);
+        let _ = writeln!(ground_truth, "part 2: {}", largest_distance);
+
+        GeneratedInput {
+            input,
+            ground_truth: Some(ground_truth),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use anyhow::Result;
+use nalgebra as na;
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+use utils::parser::{
+    blank_line, delimited, integer, line_ending, map, parse_all, section, separated_list1,
+    separated_pair, tag, Parser,
+};
+use utils::Solution;
+
+mod generator;
+
+const ROTATIONS: [na::Matrix3<i64>; 24] = [
+    na::matrix![1, 0, 0;
+                0, 1, 0;
+                0, 0, 1],
+    na::matrix![1, 0, 0;
+                0, 0, -1;
+                0, 1, 0],
+    na::matrix![1, 0, 0;
+                0, -1, 0;
+                0, 0, -1],
+    na::matrix![1, 0, 0;
+                0, 0, 1;
+                0, -1, 0],
+    na::matrix![0, -1, 0;
+                1, 0, 0;
+                0, 0, 1],
+    na::matrix![0, 0, 1;
+                1, 0, 0;
+                0, 1, 0],
+    na::matrix![0, 1, 0;
+                1, 0, 0;
+                0, 0, -1],
+    na::matrix![0, 0, -1;
+                1, 0, 0;
+                0, -1, 0],
+    na::matrix![-1, 0, 0;
+                0, -1, 0;
+                0, 0, 1],
+    na::matrix![-1, 0, 0;
+                0, 0, -1;
+                0, -1, 0],
+    na::matrix![-1, 0, 0;
+                0, 1, 0;
+                0, 0, -1],
+    na::matrix![-1, 0, 0;
+                0, 0, 1;
+                0, 1, 0],
+    na::matrix![0, 1, 0;
+                -1, 0, 0;
+                0, 0, 1],
+    na::matrix![0, 0, 1;
+                -1, 0, 0;
+                0, -1, 0],
+    na::matrix![0, -1, 0;
+                -1, 0, 0;
+                0, 0, -1],
+    na::matrix![0, 0, -1;
+                -1, 0, 0;
+                0, 1, 0],
+    na::matrix![0, 0, -1;
+                0, 1, 0;
+                1, 0, 0],
+    na::matrix![0, 1, 0;
+                0, 0, 1;
+                1, 0, 0],
+    na::matrix![0, 0, 1;
+                0, -1, 0;
+                1, 0, 0],
+    na::matrix![0, -1, 0;
+                0, 0, -1;
+                1, 0, 0],
+    na::matrix![0, 0, -1;
+                0, -1, 0;
+                -1, 0, 0],
+    na::matrix![0, -1, 0;
+                0, 0, 1;
+                -1, 0, 0],
+    na::matrix![0, 0, 1;
+                0, 1, 0;
+                -1, 0, 0],
+    na::matrix![0, 1, 0;
+                0, 0, -1;
+                -1, 0, 0],
+];
+
+#[derive(Debug)]
+pub struct Scanner {
+    beacons: HashSet<na::Point3<i64>>,
+    id: usize,
+}
+
+#[derive(Debug)]
+pub struct ScannerMap(Vec<Scanner>);
+
+fn beacon<'a>() -> impl Parser<'a, na::Point3<i64>> {
+    map(
+        separated_pair(
+            integer(),
+            tag(","),
+            separated_pair(integer(), tag(","), integer()),
+        ),
+        |(x, (y, z))| na::Point3::new(x, y, z),
+    )
+}
+
+fn scanner<'a>() -> impl Parser<'a, Scanner> {
+    map(
+        separated_pair(
+            delimited(tag("--- scanner "), integer(), tag(" ---")),
+            line_ending(),
+            section(beacon()),
+        ),
+        |(id, beacons)| Scanner {
+            id,
+            beacons: beacons.into_iter().collect(),
+        },
+    )
+}
+
+impl FromStr for Scanner {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(s, scanner())?)
+    }
+}
+
+impl FromStr for ScannerMap {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse_all(
+            s,
+            separated_list1(scanner(), blank_line()),
+        )?))
+    }
+}
+
+fn point_cloud_distances(
+    cloud: &HashSet<na::Point3<i64>>,
+) -> HashMap<na::Point3<i64>, HashSet<i64>> {
+    let mut result = HashMap::new();
+    for beacon in cloud.iter() {
+        let mut distances = HashSet::new();
+        for beacon2 in cloud.iter() {
+            let distance = (beacon - beacon2).abs().sum();
+            distances.insert(distance);
+        }
+
+        result.insert(beacon.to_owned(), distances);
+    }
+
+    result
+}
+
+type DistanceMap = HashMap<na::Point3<i64>, HashSet<i64>>;
+
+fn find_match(
+    origin_dmap: &DistanceMap,
+    unknown_dmap: &DistanceMap,
+) -> Option<(na::Point3<i64>, na::Point3<i64>)> {
+    for (orig_point, orig_distances) in origin_dmap.iter() {
+        for (unk_point, unk_distances) in unknown_dmap.iter() {
+            if orig_distances.intersection(unk_distances).count() >= 12 {
+                return Some((orig_point.to_owned(), unk_point.to_owned()));
+            }
+        }
+    }
+
+    None
+}
+
+impl Scanner {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Beacons relative to the scanner, in its own orientation.
+    pub fn beacons(&self) -> &HashSet<na::Point3<i64>> {
+        &self.beacons
+    }
+
+    fn distances(&self) -> HashMap<na::Point3<i64>, HashSet<i64>> {
+        point_cloud_distances(&self.beacons)
+    }
+}
+
+fn scanner_positions(scanners: &[Scanner]) -> (Vec<na::Point3<i64>>, HashSet<na::Point3<i64>>) {
+    let mut origins: Vec<na::Point3<i64>> = vec![na::Point3::origin()];
+    let mut ids = vec![scanners[0].id];
+    let mut known_cloud: HashSet<na::Point3<i64>> = HashSet::new();
+    known_cloud.extend(&scanners[0].beacons);
+
+    while origins.len() < scanners.len() {
+        let orig_distances = point_cloud_distances(&known_cloud);
+
+        for unknown in scanners.iter() {
+            if ids.contains(&unknown.id) {
+                continue;
+            }
+
+            if let Some((orig_point, unk_point)) = find_match(&orig_distances, &unknown.distances())
+            {
+                for matrix in ROTATIONS {
+                    let unk_point = matrix * unk_point;
+                    let translation_v = orig_point - unk_point;
+
+                    let translated_points = unknown
+                        .beacons
+                        .iter()
+                        .cloned()
+                        .map(|point| matrix * point + translation_v)
+                        .collect::<HashSet<_>>();
+
+                    if translated_points.intersection(&known_cloud).count() >= 12 {
+                        known_cloud.extend(translated_points);
+                        origins.push(translation_v.into());
+                        ids.push(unknown.id);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    (origins, known_cloud)
+}
+
+impl ScannerMap {
+    pub fn scanners(&self) -> &[Scanner] {
+        &self.0
+    }
+
+    /// Positions of scanners in the order they were located and all beacons, both relative to
+    /// the first scanner.
+    pub fn assemble(&self) -> (Vec<na::Point3<i64>>, HashSet<na::Point3<i64>>) {
+        scanner_positions(&self.0)
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = ScannerMap;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(scanner_map: &Self::Input) -> Result<Self::Part1> {
+        let (_, points) = scanner_map.assemble();
+
+        Ok(points.len())
+    }
+
+    fn part2(scanner_map: &Self::Input) -> Result<Self::Part2> {
+        let (origins, _) = scanner_map.assemble();
+
+        let mut distances = vec![];
+        for origin in origins.iter() {
+            for origin2 in origins.iter() {
+                distances.push((origin - origin2).abs().sum());
+            }
+        }
+
+        Ok(distances.into_iter().max().unwrap_or_default())
+    }
+}
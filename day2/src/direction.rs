@@ -1,63 +1,418 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+use utils::parser::Input;
 
-#[derive(Debug)]
-pub struct DirectionInvalidFormat;
+/// Why a command could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidFormat {
+    UnknownCommand(String),
+    MissingMagnitude,
+    /// Not an integer, too large, or negative where only a distance makes sense.
+    BadMagnitude(String),
+    MissingRepeatCount,
+    BadRepeatCount(String),
+    /// `repeat N` not followed by `{`.
+    MissingBlock,
+    UnclosedBlock,
+    UnmatchedBrace,
+    TrailingInput(String),
+    /// The route would unroll to more than [`Route::MAX_COMMANDS`](crate::Route::MAX_COMMANDS).
+    TooLong,
+}
+
+impl Display for InvalidFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidFormat::UnknownCommand(command) if command.is_empty() => {
+                write!(f, "missing command")
+            }
+            InvalidFormat::UnknownCommand(command) => write!(f, "unknown command {:?}", command),
+            InvalidFormat::MissingMagnitude => write!(f, "missing magnitude"),
+            InvalidFormat::BadMagnitude(magnitude) => write!(f, "bad magnitude {:?}", magnitude),
+            InvalidFormat::MissingRepeatCount => write!(f, "missing repeat count"),
+            InvalidFormat::BadRepeatCount(count) => write!(f, "bad repeat count {:?}", count),
+            InvalidFormat::MissingBlock => write!(f, "expected {{ after the repeat count"),
+            InvalidFormat::UnclosedBlock => write!(f, "repeat block is never closed"),
+            InvalidFormat::UnmatchedBrace => write!(f, "}} without a repeat block"),
+            InvalidFormat::TrailingInput(input) => {
+                write!(f, "unexpected {:?} after the command", input)
+            }
+            InvalidFormat::TooLong => write!(
+                f,
+                "route runs longer than {} commands",
+                crate::Route::MAX_COMMANDS
+            ),
+        }
+    }
+}
+
+/// Malformed line of a route script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionInvalidFormat {
+    /// 1-based line number.
+    pub line: usize,
+    /// The line as written, without surrounding whitespace.
+    pub text: String,
+    pub reason: InvalidFormat,
+}
+
+impl DirectionInvalidFormat {
+    pub(crate) fn at(input: Input<'_>, reason: InvalidFormat) -> Self {
+        Self {
+            line: input.line(),
+            text: input.source_line().trim().to_owned(),
+            reason,
+        }
+    }
+}
 
 impl Display for DirectionInvalidFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?} - failed to parse direction format", self)
+        write!(f, "line {} ({:?}): {}", self.line, self.text, self.reason)
     }
 }
 
 impl Error for DirectionInvalidFormat {}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
-    Up(usize),
-    Down(usize),
-    Forward(usize),
+    Up(i64),
+    Down(i64),
+    Forward(i64),
+    Backward(i64),
+    SetAim(i64),
+    /// Moves the submarine back to the surface at the starting point, with a level aim.
+    Reset,
+}
+
+/// Whitespace, line breaks and `#` comments running to the end of the line.
+pub(crate) fn trivia(input: Input<'_>) -> Input<'_> {
+    let mut input = input;
+
+    loop {
+        let rest = input.rest();
+        let trimmed = rest.trim_start();
+        input = input.advance(rest.len() - trimmed.len());
+
+        if !trimmed.starts_with('#') {
+            return input;
+        }
+        input = input.advance(trimmed.find('\n').unwrap_or(trimmed.len()));
+    }
+}
+
+/// Next word on the current line after any spaces - everything up to whitespace, a comment or
+/// a brace. Empty at the end of the line.
+pub(crate) fn token(input: Input<'_>) -> (&str, Input<'_>) {
+    let rest = input.rest();
+    let trimmed = rest.trim_start_matches([' ', '\t']);
+    let input = input.advance(rest.len() - trimmed.len());
+    let len = trimmed
+        .find(|c: char| c.is_whitespace() || matches!(c, '#' | '{' | '}'))
+        .unwrap_or(trimmed.len());
+
+    (&trimmed[..len], input.advance(len))
+}
+
+/// Single command, e.g. `forward 5`, `set-aim -2` or `reset`.
+pub(crate) fn direction(input: Input<'_>) -> Result<(Direction, Input<'_>), InvalidFormat> {
+    let (verb, input) = token(input);
+    let movement = match verb {
+        "forward" => Direction::Forward as fn(i64) -> Direction,
+        "backward" => Direction::Backward,
+        "up" => Direction::Up,
+        "down" => Direction::Down,
+        "set-aim" => Direction::SetAim,
+        "reset" => return Ok((Direction::Reset, input)),
+        _ if verb.is_empty() => {
+            let symbol = input.rest().chars().take(1).collect();
+            return Err(InvalidFormat::UnknownCommand(symbol));
+        }
+        _ => return Err(InvalidFormat::UnknownCommand(verb.to_owned())),
+    };
+
+    let (magnitude, input) = token(input);
+    if magnitude.is_empty() {
+        return Err(InvalidFormat::MissingMagnitude);
+    }
+
+    magnitude
+        .parse::<i64>()
+        .ok()
+        .filter(|units| verb == "set-aim" || *units >= 0)
+        .map(|units| (movement(units), input))
+        .ok_or_else(|| InvalidFormat::BadMagnitude(magnitude.to_owned()))
 }
 
 impl FromStr for Direction {
     type Err = DirectionInvalidFormat;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut splitted = s.split_ascii_whitespace();
-        let command = splitted.next().ok_or(DirectionInvalidFormat)?;
-        let units = splitted
-            .next()
-            .ok_or(DirectionInvalidFormat)?
-            .parse::<usize>()
-            .map_err(|_| DirectionInvalidFormat)?;
+        let start = trivia(Input::new(s));
+        let (direction, rest) =
+            direction(start).map_err(|reason| DirectionInvalidFormat::at(start, reason))?;
+
+        let rest = trivia(rest);
+        if !rest.is_empty() {
+            let (trailing, _) = token(rest);
+            let trailing = if trailing.is_empty() {
+                rest.rest().chars().take(1).collect()
+            } else {
+                trailing.to_owned()
+            };
+
+            return Err(DirectionInvalidFormat::at(
+                rest,
+                InvalidFormat::TrailingInput(trailing),
+            ));
+        }
+
+        Ok(direction)
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Forward(u) => write!(f, "forward {}", u),
+            Self::Backward(u) => write!(f, "backward {}", u),
+            Self::Up(u) => write!(f, "up {}", u),
+            Self::Down(u) => write!(f, "down {}", u),
+            Self::SetAim(aim) => write!(f, "set-aim {}", aim),
+            Self::Reset => write!(f, "reset"),
+        }
+    }
+}
+
+/// State of the submarine. Depth grows downwards from the surface at 0, a negative aim points up.
+/// Aim is only used by the aimed model.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub horizontal: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "horizontal {}, depth {}, aim {}",
+            self.horizontal, self.depth, self.aim
+        )
+    }
+}
+
+/// Invariant of the submarine's position broken by a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    AboveSurface,
+    Overflow,
+}
 
-        match command {
-            "forward" => Ok(Direction::Forward(units)),
-            "up" => Ok(Direction::Up(units)),
-            "down" => Ok(Direction::Down(units)),
-            _ => Err(DirectionInvalidFormat),
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::AboveSurface => write!(f, "takes the submarine above the surface"),
+            Violation::Overflow => write!(f, "overflows the position"),
         }
     }
 }
 
+fn check(position: Option<Position>) -> Result<Position, Violation> {
+    match position {
+        None => Err(Violation::Overflow),
+        Some(position) if position.depth < 0 => Err(Violation::AboveSurface),
+        Some(position) => Ok(position),
+    }
+}
+
 impl Direction {
-    pub fn process(&self, (horizontal, depth): (usize, usize)) -> (usize, usize) {
+    /// Position after the command. The submarine may go above the surface, and a position past
+    /// the range of `i64` wraps around - see [`Direction::checked_process`] for a checked version.
+    pub fn process(&self, position: Position) -> Position {
+        match self {
+            Self::Forward(u) => Position {
+                horizontal: position.horizontal.wrapping_add(*u),
+                ..position
+            },
+            Self::Backward(u) => Position {
+                horizontal: position.horizontal.wrapping_sub(*u),
+                ..position
+            },
+            Self::Up(u) => Position {
+                depth: position.depth.wrapping_sub(*u),
+                ..position
+            },
+            Self::Down(u) => Position {
+                depth: position.depth.wrapping_add(*u),
+                ..position
+            },
+            Self::SetAim(aim) => Position {
+                aim: *aim,
+                ..position
+            },
+            Self::Reset => Position::default(),
+        }
+    }
+
+    /// Moving backward retraces the aim in reverse, so it also undoes the depth change. Like
+    /// [`Direction::process`], positions wrap around instead of overflowing.
+    pub fn process_aimed(&self, position: Position) -> Position {
         match self {
-            Self::Forward(u) => (horizontal + u, depth),
-            Self::Up(u) => (horizontal, depth - u),
-            Self::Down(u) => (horizontal, depth + u),
+            Self::Forward(u) => Position {
+                horizontal: position.horizontal.wrapping_add(*u),
+                depth: position.depth.wrapping_add(position.aim.wrapping_mul(*u)),
+                ..position
+            },
+            Self::Backward(u) => Position {
+                horizontal: position.horizontal.wrapping_sub(*u),
+                depth: position.depth.wrapping_sub(position.aim.wrapping_mul(*u)),
+                ..position
+            },
+            Self::Up(u) => Position {
+                aim: position.aim.wrapping_sub(*u),
+                ..position
+            },
+            Self::Down(u) => Position {
+                aim: position.aim.wrapping_add(*u),
+                ..position
+            },
+            Self::SetAim(aim) => Position {
+                aim: *aim,
+                ..position
+            },
+            Self::Reset => Position::default(),
         }
     }
 
-    pub fn process_aimed(
+    /// Like [`Direction::process`], but fails instead of going above the surface or overflowing.
+    pub fn checked_process(&self, position: Position) -> Result<Position, Violation> {
+        check(match self {
+            Self::Forward(u) => position
+                .horizontal
+                .checked_add(*u)
+                .map(|horizontal| Position {
+                    horizontal,
+                    ..position
+                }),
+            Self::Backward(u) => position
+                .horizontal
+                .checked_sub(*u)
+                .map(|horizontal| Position {
+                    horizontal,
+                    ..position
+                }),
+            Self::Up(u) => position
+                .depth
+                .checked_sub(*u)
+                .map(|depth| Position { depth, ..position }),
+            Self::Down(u) => position
+                .depth
+                .checked_add(*u)
+                .map(|depth| Position { depth, ..position }),
+            Self::SetAim(_) | Self::Reset => Some(self.process(position)),
+        })
+    }
+
+    /// Like [`Direction::process_aimed`], but fails instead of going above the surface or overflowing.
+    pub fn checked_process_aimed(&self, position: Position) -> Result<Position, Violation> {
+        check(match self {
+            Self::Forward(u) => position.horizontal.checked_add(*u).and_then(|horizontal| {
+                let depth = position.depth.checked_add(position.aim.checked_mul(*u)?)?;
+
+                Some(Position {
+                    horizontal,
+                    depth,
+                    ..position
+                })
+            }),
+            Self::Backward(u) => position.horizontal.checked_sub(*u).and_then(|horizontal| {
+                let depth = position.depth.checked_sub(position.aim.checked_mul(*u)?)?;
+
+                Some(Position {
+                    horizontal,
+                    depth,
+                    ..position
+                })
+            }),
+            Self::Up(u) => position
+                .aim
+                .checked_sub(*u)
+                .map(|aim| Position { aim, ..position }),
+            Self::Down(u) => position
+                .aim
+                .checked_add(*u)
+                .map(|aim| Position { aim, ..position }),
+            Self::SetAim(_) | Self::Reset => Some(self.process_aimed(position)),
+        })
+    }
+}
+
+/// How the submarine interprets its commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    /// Up and down change the depth directly.
+    Plain,
+    /// Up and down change the aim, which sets the depth change of every forward move.
+    Aimed,
+}
+
+impl Model {
+    /// Wraps around on overflow, see [`Direction::process`].
+    pub fn process(&self, direction: &Direction, position: Position) -> Position {
+        match self {
+            Model::Plain => direction.process(position),
+            Model::Aimed => direction.process_aimed(position),
+        }
+    }
+
+    pub fn checked_process(
         &self,
-        (horizontal, depth, aim): (usize, usize, usize),
-    ) -> (usize, usize, usize) {
+        direction: &Direction,
+        position: Position,
+    ) -> Result<Position, Violation> {
         match self {
-            Self::Forward(u) => (horizontal + u, depth + aim * u, aim),
-            Self::Up(u) => (horizontal, depth, aim - u),
-            Self::Down(u) => (horizontal, depth, aim + u),
+            Model::Plain => direction.checked_process(position),
+            Model::Aimed => direction.checked_process_aimed(position),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchecked_moves_wrap_around_where_checked_ones_fail() {
+        let position = Position {
+            horizontal: 0,
+            depth: 0,
+            aim: 10_000_000_000,
+        };
+        let direction = Direction::Forward(10_000_000_000);
+
+        assert_eq!(
+            direction.process_aimed(position).depth,
+            10_000_000_000i64.wrapping_mul(10_000_000_000)
+        );
+        assert_eq!(
+            direction.checked_process_aimed(position),
+            Err(Violation::Overflow)
+        );
+
+        let position = Position {
+            horizontal: i64::MIN,
+            ..Position::default()
+        };
+        assert_eq!(
+            Direction::Backward(1).process(position).horizontal,
+            i64::MAX
+        );
+        assert_eq!(
+            Direction::Backward(1).checked_process(position),
+            Err(Violation::Overflow)
+        );
+    }
+}
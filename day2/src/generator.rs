@@ -0,0 +1,32 @@
+use crate::Day2;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day2 {
+    const DEFAULT_SIZE: usize = 1000;
+
+    /// `size` commands. The submarine never rises above the surface, so the aim is never negative either.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let mut depth = 0;
+
+        (0..size)
+            .map(|_| {
+                let units = rng.gen_range(1..=9);
+
+                match rng.gen_range(0..3) {
+                    0 => format!("forward {}\n", units),
+                    1 if depth >= units => {
+                        depth -= units;
+                        format!("up {}\n", units)
+                    }
+                    _ => {
+                        depth += units;
+                        format!("down {}\n", units)
+                    }
+                }
+            })
+            .collect::<String>()
+            .into()
+    }
+}
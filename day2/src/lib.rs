@@ -0,0 +1,119 @@
+use anyhow::{anyhow, Result};
+use std::error::Error;
+use std::fmt::Display;
+
+mod direction;
+mod generator;
+mod route;
+mod trajectory;
+pub use direction::{Direction, DirectionInvalidFormat, InvalidFormat, Model, Position, Violation};
+pub use route::{Command, Directions, Route};
+pub use trajectory::{Interrupted, Interruption, Trajectory};
+use utils::Solution;
+
+/// Command which broke an invariant during checked navigation, with the position right before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NavigationError {
+    /// 0-based index of the command among the executed ones, with `repeat` blocks unrolled.
+    pub index: usize,
+    pub direction: Direction,
+    pub position: Position,
+    pub violation: Violation,
+}
+
+impl Display for NavigationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "command #{} ({}) {} at {}",
+            self.index, self.direction, self.violation, self.position
+        )
+    }
+}
+
+impl Error for NavigationError {}
+
+/// Horizontal position multiplied by depth, the answer to both parts.
+fn multiplied(position: Position) -> Result<i64> {
+    position
+        .horizontal
+        .checked_mul(position.depth)
+        .ok_or_else(|| anyhow!("horizontal position times depth overflows at {}", position))
+}
+
+fn navigate_checked(
+    directions: impl IntoIterator<Item = Direction>,
+    model: Model,
+) -> Result<Position, NavigationError> {
+    directions.into_iter().enumerate().try_fold(
+        Position::default(),
+        |position, (index, direction)| {
+            model
+                .checked_process(&direction, position)
+                .map_err(|violation| NavigationError {
+                    index,
+                    direction,
+                    position,
+                    violation,
+                })
+        },
+    )
+}
+
+/// Final position of the submarine, wrapping around if it leaves the range of `i64`.
+pub fn final_shuttle_position(directions: impl IntoIterator<Item = Direction>) -> Position {
+    directions
+        .into_iter()
+        .fold(Position::default(), |position, direction| {
+            direction.process(position)
+        })
+}
+
+/// Final position of the submarine, with up and down changing the aim instead. Wraps around
+/// like [`final_shuttle_position`].
+pub fn final_shuttle_position_aimed(directions: impl IntoIterator<Item = Direction>) -> Position {
+    directions
+        .into_iter()
+        .fold(Position::default(), |position, direction| {
+            direction.process_aimed(position)
+        })
+}
+
+/// Like [`final_shuttle_position`], but stops at the first command which takes the submarine
+/// above the surface or overflows its position.
+pub fn checked_shuttle_position(
+    directions: impl IntoIterator<Item = Direction>,
+) -> Result<Position, NavigationError> {
+    navigate_checked(directions, Model::Plain)
+}
+
+/// Like [`final_shuttle_position_aimed`], but stops at the first command which takes the
+/// submarine above the surface or overflows its position.
+pub fn checked_shuttle_position_aimed(
+    directions: impl IntoIterator<Item = Direction>,
+) -> Result<Position, NavigationError> {
+    navigate_checked(directions, Model::Aimed)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Route;
+    type Part1 = i64;
+    type Part2 = i64;
+    // Navigation used to wrap around on overflow and let the submarine go above the surface,
+    // and the answers were multiplied without an overflow check.
+    const VERSION: u32 = 2;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(route: &Self::Input) -> Result<Self::Part1> {
+        multiplied(checked_shuttle_position(route.directions())?)
+    }
+
+    fn part2(route: &Self::Input) -> Result<Self::Part2> {
+        multiplied(checked_shuttle_position_aimed(route.directions())?)
+    }
+}
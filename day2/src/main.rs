@@ -1,46 +1,98 @@
-use std::error::Error;
-use std::fs;
-use std::path::Path;
-
-mod direction;
-use direction::Direction;
-
-fn read_all(path: impl AsRef<Path>) -> Result<Vec<Direction>, Box<dyn Error>> {
-    Ok(fs::read_to_string(path)?
-        .lines()
-        .flat_map(str::parse)
-        .collect())
-}
+use anyhow::{anyhow, Context, Result};
+use day2::{Day2, Interrupted, Model, Route, Trajectory};
+use std::env;
+use utils::input;
+use utils::Solution;
+
+const USAGE: &str =
+    "usage: day2 [<path|->] [--lenient] [--trajectory <csv|svg>] [--model <plain|aimed>]";
 
-fn final_shuttle_position(directions: &[Direction]) -> (usize, usize) {
-    directions
-        .iter()
-        .fold((0, 0), |total, direction| direction.process(total))
+enum TrajectoryFormat {
+    Csv,
+    Svg,
 }
 
-fn final_shuttle_position_aimed(directions: &[Direction]) -> (usize, usize) {
-    let result = directions
-        .iter()
-        .fold((0, 0, 0), |total, direction| direction.process_aimed(total));
+fn main() -> Result<()> {
+    let mut path = None;
+    let mut trajectory = None;
+    let mut model = None;
+    let mut lenient = false;
 
-    (result.0, result.1)
-}
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trajectory" => {
+                let format = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--trajectory requires csv or svg"))?;
+                trajectory = match format.as_str() {
+                    "csv" => Some(TrajectoryFormat::Csv),
+                    "svg" => Some(TrajectoryFormat::Svg),
+                    _ => return Err(anyhow!("unknown format {}, expected csv or svg", format)),
+                };
+            }
+            "--model" => {
+                let name = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--model requires plain or aimed"))?;
+                model = match name.as_str() {
+                    "plain" => Some(Model::Plain),
+                    "aimed" => Some(Model::Aimed),
+                    _ => return Err(anyhow!("unknown model {}, expected plain or aimed", name)),
+                };
+            }
+            "--lenient" => lenient = true,
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(anyhow!("unknown argument {}\n{}", arg, USAGE)),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| "./input".into());
+    let script = input::read(&path)?;
+    let route = if lenient {
+        let (route, skipped) = Route::parse_lenient(&script);
+        if !skipped.is_empty() {
+            eprintln!("skipped {} malformed lines of {}:", skipped.len(), path);
+            for error in skipped.iter() {
+                eprintln!("  {}", error);
+            }
+        }
+
+        route
+    } else {
+        Day2::parse(&script).with_context(|| format!("malformed route {}", path))?
+    };
+
+    match trajectory {
+        Some(format) => {
+            // An interrupted trajectory is still exported up to the failing command, the error
+            // is reported afterwards.
+            let (trajectory, interruption) =
+                match Trajectory::record(model.unwrap_or(Model::Aimed), route.directions()) {
+                    Ok(trajectory) => (trajectory, None),
+                    Err(Interrupted { trajectory, reason }) => (trajectory, Some(reason)),
+                };
+
+            match format {
+                TrajectoryFormat::Csv => print!("{}", trajectory.to_csv()),
+                TrajectoryFormat::Svg => print!("{}", trajectory.to_svg()),
+            }
+
+            if let Some(reason) = interruption {
+                return Err(Interrupted { trajectory, reason }.into());
+            }
+        }
+        None if model.is_some() => {
+            return Err(anyhow!("--model only applies to --trajectory\n{}", USAGE));
+        }
+        None => {
+            println!("shuttle position multiplied is {}", Day2::part1(&route)?);
+            println!(
+                "shuttle position with aim multiplied is {}",
+                Day2::part2(&route)?
+            );
+        }
+    }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let directions = read_all("./input")?;
-
-    let final_pos = final_shuttle_position(&directions);
-    let final_pos_aimed = final_shuttle_position_aimed(&directions);
-
-    println!(
-        "shuttle position {:?}, multiplied is {}",
-        final_pos,
-        final_pos.0 * final_pos.1
-    );
-    println!(
-        "shuttle position with aim {:?}, multiplied is {}",
-        final_pos_aimed,
-        final_pos_aimed.0 * final_pos_aimed.1
-    );
     Ok(())
 }
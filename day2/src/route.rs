@@ -0,0 +1,466 @@
+use crate::direction::{
+    direction, token, trivia, Direction, DirectionInvalidFormat, InvalidFormat,
+};
+use std::str::FromStr;
+use utils::parser::Input;
+
+/// Command of a route script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Direction(Direction),
+    /// `repeat N { ... }` - runs the body `times` times in a row.
+    Repeat {
+        times: usize,
+        body: Vec<Command>,
+    },
+}
+
+impl Command {
+    /// Number of commands the submarine executes for this one, `None` if it does not fit `usize`.
+    fn unrolled_len(&self) -> Option<usize> {
+        match self {
+            Command::Direction(_) => Some(1),
+            Command::Repeat { times, body } => body
+                .iter()
+                .try_fold(0usize, |len, command| {
+                    len.checked_add(command.unrolled_len()?)
+                })?
+                .checked_mul(*times),
+        }
+    }
+}
+
+/// Route script of the submarine. Besides the puzzle's commands, scripts can use `backward N`,
+/// `set-aim N` and `reset`, `#` comments and possibly nested `repeat N { ... }` blocks:
+///
+/// ```text
+/// # dive, then sweep the trench twice
+/// down 5
+/// repeat 2 {
+///     forward 10
+///     backward 10
+/// }
+/// ```
+///
+/// Commands are separated by whitespace, so a short block fits on one line: `repeat 3 { up 1 }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Route(Vec<Command>);
+
+/// Recursive descent over a route script. Strict parsing stops at the first malformed line,
+/// lenient parsing records it and carries on with the next line.
+struct ScriptParser {
+    lenient: bool,
+    skipped: Vec<DirectionInvalidFormat>,
+}
+
+type Parsed<'a, T> = Result<(T, Input<'a>), DirectionInvalidFormat>;
+
+impl ScriptParser {
+    fn reject(&mut self, error: DirectionInvalidFormat) -> Result<(), DirectionInvalidFormat> {
+        if self.lenient {
+            self.skipped.push(error);
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    fn repeat<'a>(&mut self, start: Input<'a>, input: Input<'a>) -> Parsed<'a, Command> {
+        let invalid = |reason| DirectionInvalidFormat::at(start, reason);
+
+        let (count, input) = token(input);
+        if count.is_empty() {
+            return Err(invalid(InvalidFormat::MissingRepeatCount));
+        }
+        let times = count
+            .parse()
+            .map_err(|_| invalid(InvalidFormat::BadRepeatCount(count.to_owned())))?;
+
+        let input = trivia(input);
+        if !input.rest().starts_with('{') {
+            return Err(invalid(InvalidFormat::MissingBlock));
+        }
+
+        let (body, input) = self.block(input.advance(1), Some(start))?;
+        let input = if input.rest().starts_with('}') {
+            input.advance(1)
+        } else {
+            input
+        };
+
+        Ok((Command::Repeat { times, body }, input))
+    }
+
+    fn command<'a>(&mut self, input: Input<'a>) -> Parsed<'a, Command> {
+        match token(input) {
+            ("repeat", rest) => self.repeat(input, rest),
+            _ => direction(input)
+                .map(|(direction, rest)| (Command::Direction(direction), rest))
+                .map_err(|reason| DirectionInvalidFormat::at(input, reason)),
+        }
+    }
+
+    /// Commands up to the end of the input or the `}` closing the block opened at `opening`.
+    fn block<'a>(
+        &mut self,
+        input: Input<'a>,
+        opening: Option<Input<'a>>,
+    ) -> Parsed<'a, Vec<Command>> {
+        let mut commands = vec![];
+        let mut len = 0usize;
+        let mut input = trivia(input);
+
+        loop {
+            if input.is_empty() {
+                if let Some(opening) = opening {
+                    self.reject(DirectionInvalidFormat::at(
+                        opening,
+                        InvalidFormat::UnclosedBlock,
+                    ))?;
+                }
+
+                return Ok((commands, input));
+            }
+
+            let error = if input.rest().starts_with('}') {
+                if opening.is_some() {
+                    return Ok((commands, input));
+                }
+
+                DirectionInvalidFormat::at(input, InvalidFormat::UnmatchedBrace)
+            } else {
+                match self.command(input) {
+                    Ok((command, rest)) => {
+                        let unrolled = command
+                            .unrolled_len()
+                            .and_then(|command_len| len.checked_add(command_len))
+                            .filter(|&unrolled| unrolled <= Route::MAX_COMMANDS);
+
+                        match unrolled {
+                            Some(unrolled) => {
+                                len = unrolled;
+                                commands.push(command);
+                            }
+                            None => self.reject(DirectionInvalidFormat::at(
+                                input,
+                                InvalidFormat::TooLong,
+                            ))?,
+                        }
+
+                        input = trivia(rest);
+                        continue;
+                    }
+                    Err(error) => error,
+                }
+            };
+
+            self.reject(error)?;
+            input = trivia(skip_line(input));
+        }
+    }
+}
+
+fn skip_line(input: Input<'_>) -> Input<'_> {
+    let rest = input.rest();
+    input.advance(rest.find('\n').map_or(rest.len(), |idx| idx + 1))
+}
+
+impl FromStr for Route {
+    type Err = DirectionInvalidFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = ScriptParser {
+            lenient: false,
+            skipped: vec![],
+        };
+        let (commands, _) = parser.block(Input::new(s), None)?;
+
+        Ok(Self(commands))
+    }
+}
+
+impl FromIterator<Direction> for Route {
+    fn from_iter<T: IntoIterator<Item = Direction>>(iter: T) -> Self {
+        Self(iter.into_iter().map(Command::Direction).collect())
+    }
+}
+
+impl Route {
+    /// Longest route a script may unroll to, so that following it always finishes.
+    pub const MAX_COMMANDS: usize = 100_000_000;
+
+    /// Parses the script skipping every malformed line instead of failing on the first one.
+    /// Returns what could be parsed together with all the skipped lines.
+    pub fn parse_lenient(s: &str) -> (Self, Vec<DirectionInvalidFormat>) {
+        let mut parser = ScriptParser {
+            lenient: true,
+            skipped: vec![],
+        };
+        // Lenient parsing never fails, malformed lines end up in `skipped` instead.
+        let commands = parser
+            .block(Input::new(s), None)
+            .map(|(commands, _)| commands)
+            .unwrap_or_default();
+
+        (Self(commands), parser.skipped)
+    }
+
+    pub fn commands(&self) -> &[Command] {
+        &self.0
+    }
+
+    /// Every command the submarine executes, in order, with `repeat` blocks unrolled lazily.
+    pub fn directions(&self) -> Directions<'_> {
+        Directions {
+            stack: vec![Frame {
+                body: &self.0,
+                next: 0,
+                remaining: 0,
+            }],
+        }
+    }
+}
+
+struct Frame<'a> {
+    body: &'a [Command],
+    next: usize,
+    /// Runs of the body still left after the current one.
+    remaining: usize,
+}
+
+/// Iterator returned by [`Route::directions`].
+pub struct Directions<'a> {
+    stack: Vec<Frame<'a>>,
+}
+
+impl Iterator for Directions<'_> {
+    type Item = Direction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            let body = frame.body;
+
+            match body.get(frame.next) {
+                Some(Command::Direction(direction)) => {
+                    frame.next += 1;
+                    return Some(*direction);
+                }
+                Some(Command::Repeat { times, body }) => {
+                    frame.next += 1;
+                    if *times > 0 && !body.is_empty() {
+                        self.stack.push(Frame {
+                            body,
+                            next: 0,
+                            remaining: times - 1,
+                        });
+                    }
+                }
+                None if frame.remaining > 0 => {
+                    frame.remaining -= 1;
+                    frame.next = 0;
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{checked_shuttle_position, Violation};
+    use Direction::*;
+
+    fn directions(script: &str) -> Vec<Direction> {
+        let route: Route = script.parse().expect("script should parse");
+        route.directions().collect()
+    }
+
+    #[test]
+    fn unrolls_nested_repeat_blocks() {
+        let script = "down 1\nrepeat 2 {\n    forward 3\n    repeat 2 { up 1 }\n}\nreset\n";
+
+        assert_eq!(
+            directions(script),
+            [
+                Down(1),
+                Forward(3),
+                Up(1),
+                Up(1),
+                Forward(3),
+                Up(1),
+                Up(1),
+                Reset
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_comments() {
+        let script = "# dive\ndown 5 # all the way\n\n  # sweep\nrepeat 2 { forward 1 } # twice\n";
+
+        assert_eq!(directions(script), [Down(5), Forward(1), Forward(1)]);
+    }
+
+    #[test]
+    fn empty_and_zero_repeats_execute_nothing() {
+        let route: Route = "repeat 0 { forward 1 }\nrepeat 3 {}\nrepeat 2 { # nothing\n}\nup 1"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            route.commands()[..2],
+            [
+                Command::Repeat {
+                    times: 0,
+                    body: vec![Command::Direction(Forward(1))],
+                },
+                Command::Repeat {
+                    times: 3,
+                    body: vec![],
+                },
+            ]
+        );
+        assert_eq!(route.directions().collect::<Vec<_>>(), [Up(1)]);
+        assert_eq!(directions("repeat 2 { repeat 0 { up 1 } }"), []);
+        assert_eq!(directions(""), []);
+    }
+
+    #[test]
+    fn navigation_errors_index_unrolled_commands_from_zero() {
+        let route: Route = "down 1\nrepeat 2 { repeat 2 { up 1 } }\nforward 1"
+            .parse()
+            .unwrap();
+        let error = checked_shuttle_position(route.directions()).unwrap_err();
+
+        assert_eq!(error.index, 2);
+        assert_eq!(error.direction, Up(1));
+        assert_eq!(error.violation, Violation::AboveSurface);
+    }
+
+    fn strict_error(script: &str) -> DirectionInvalidFormat {
+        script
+            .parse::<Route>()
+            .expect_err("script should be rejected")
+    }
+
+    #[test]
+    fn reports_one_based_lines_as_written() {
+        let error = strict_error("\n# start\ndown 1\n  forward -1  \nup 1\n");
+
+        assert_eq!(error.line, 4);
+        assert_eq!(error.text, "forward -1");
+        assert_eq!(error.reason, InvalidFormat::BadMagnitude("-1".into()));
+
+        let error = strict_error("sideways 3\n");
+        assert_eq!(error.line, 1);
+        assert_eq!(
+            error.reason,
+            InvalidFormat::UnknownCommand("sideways".into())
+        );
+    }
+
+    #[test]
+    fn reports_malformed_blocks() {
+        let error = strict_error("down 1\nrepeat 2 {\n  up 1\n");
+        assert_eq!(
+            (error.line, error.reason),
+            (2, InvalidFormat::UnclosedBlock)
+        );
+
+        let error = strict_error("repeat 3 forward 1\n");
+        assert_eq!((error.line, error.reason), (1, InvalidFormat::MissingBlock));
+
+        let error = strict_error("down 1\n}\n");
+        assert_eq!(
+            (error.line, error.reason),
+            (2, InvalidFormat::UnmatchedBrace)
+        );
+
+        let error = strict_error("repeat { up 1 }");
+        assert_eq!(error.reason, InvalidFormat::MissingRepeatCount);
+
+        let error = strict_error("repeat -1 { up 1 }");
+        assert_eq!(error.reason, InvalidFormat::BadRepeatCount("-1".into()));
+    }
+
+    #[test]
+    fn rejects_routes_unrolling_past_the_limit() {
+        let error = strict_error(
+            "down 1\nrepeat 18446744073709551615 {\n  repeat 18446744073709551615 { set-aim 1 }\n}\n",
+        );
+        assert_eq!((error.line, error.reason), (3, InvalidFormat::TooLong));
+
+        let error = strict_error("repeat 100000 {\n  repeat 100000 { up 1 }\n}\n");
+        assert_eq!((error.line, error.reason), (1, InvalidFormat::TooLong));
+
+        let error = strict_error("repeat 60000000 { up 1 }\nrepeat 60000000 { down 1 }\n");
+        assert_eq!((error.line, error.reason), (2, InvalidFormat::TooLong));
+
+        assert!("repeat 50000000 { up 1 }\nrepeat 50000000 { down 1 }\n"
+            .parse::<Route>()
+            .is_ok());
+    }
+
+    #[test]
+    fn lenient_parsing_skips_a_route_too_long_to_follow() {
+        let (route, skipped) =
+            Route::parse_lenient("up 1\nrepeat 1000 {\n  repeat 1000000 { down 1 }\n}\nup 2\n");
+
+        assert_eq!(route.directions().collect::<Vec<_>>(), [Up(1), Up(2)]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(
+            (skipped[0].line, &skipped[0].reason),
+            (2, &InvalidFormat::TooLong)
+        );
+    }
+
+    #[test]
+    fn lenient_parsing_skips_an_unmatched_brace() {
+        let (route, skipped) = Route::parse_lenient("forward 1\n}\ndown 2\n");
+
+        assert_eq!(
+            route.directions().collect::<Vec<_>>(),
+            [Forward(1), Down(2)]
+        );
+        assert_eq!(skipped.len(), 1);
+        assert_eq!((skipped[0].line, &skipped[0].text), (2, &"}".to_owned()));
+        assert_eq!(skipped[0].reason, InvalidFormat::UnmatchedBrace);
+    }
+
+    #[test]
+    fn lenient_parsing_skips_a_repeat_without_a_block() {
+        let (route, skipped) = Route::parse_lenient("repeat 3 forward 1\ndown 2\n");
+
+        assert_eq!(route.directions().collect::<Vec<_>>(), [Down(2)]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].line, 1);
+        assert_eq!(skipped[0].reason, InvalidFormat::MissingBlock);
+    }
+
+    #[test]
+    fn lenient_parsing_collects_every_malformed_line() {
+        let script =
+            "down 1\nsideways 3\nrepeat 2 {\n  up 1 }\n  forward 7 }\nrepeat 2 {\n  up 2\n";
+        let (route, skipped) = Route::parse_lenient(script);
+
+        assert_eq!(
+            route.directions().collect::<Vec<_>>(),
+            [Down(1), Up(1), Up(1), Forward(7), Up(2), Up(2)]
+        );
+        assert_eq!(
+            skipped
+                .iter()
+                .map(|error| (error.line, error.reason.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (2, InvalidFormat::UnknownCommand("sideways".into())),
+                (5, InvalidFormat::UnmatchedBrace),
+                (6, InvalidFormat::UnclosedBlock),
+            ]
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use crate::direction::{Direction, Model, Position};
+use crate::NavigationError;
+use std::error::Error;
+use std::fmt::Display;
+
+const SVG_WIDTH: f64 = 800.0;
+const SVG_HEIGHT: f64 = 400.0;
+const SVG_MARGIN: f64 = 40.0;
+
+/// Every state the submarine passes through while following a route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    model: Model,
+    directions: Vec<Direction>,
+    positions: Vec<Position>,
+}
+
+/// Why a trajectory stopped before the end of the route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Interruption {
+    Navigation(NavigationError),
+    /// The route runs longer than [`Trajectory::MAX_COMMANDS`].
+    TooLong,
+}
+
+/// Trajectory cut short, with every state recorded before it stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interrupted {
+    pub trajectory: Trajectory,
+    pub reason: Interruption,
+}
+
+impl Display for Interrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.reason {
+            Interruption::Navigation(_) => write!(
+                f,
+                "trajectory stopped after {} commands",
+                self.trajectory.directions.len()
+            ),
+            Interruption::TooLong => write!(
+                f,
+                "route is longer than {} commands",
+                Trajectory::MAX_COMMANDS
+            ),
+        }
+    }
+}
+
+impl Error for Interrupted {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.reason {
+            Interruption::Navigation(error) => Some(error),
+            Interruption::TooLong => None,
+        }
+    }
+}
+
+impl Trajectory {
+    /// Every state is kept in memory, so recording stops after this many commands.
+    pub const MAX_COMMANDS: usize = 1_000_000;
+
+    /// Follows the route with checked navigation, stopping at the first command which takes
+    /// the submarine above the surface or overflows its position. Commands are taken from
+    /// `directions` one by one, so routes with large `repeat` blocks are never unrolled in full.
+    pub fn record(
+        model: Model,
+        directions: impl IntoIterator<Item = Direction>,
+    ) -> Result<Self, Interrupted> {
+        let mut trajectory = Self {
+            model,
+            directions: vec![],
+            positions: vec![Position::default()],
+        };
+
+        for (index, direction) in directions.into_iter().enumerate() {
+            if index == Self::MAX_COMMANDS {
+                return Err(Interrupted {
+                    trajectory,
+                    reason: Interruption::TooLong,
+                });
+            }
+
+            let position = trajectory.final_position();
+            match model.checked_process(&direction, position) {
+                Ok(next) => {
+                    trajectory.directions.push(direction);
+                    trajectory.positions.push(next);
+                }
+                Err(violation) => {
+                    return Err(Interrupted {
+                        trajectory,
+                        reason: Interruption::Navigation(NavigationError {
+                            index,
+                            direction,
+                            position,
+                            violation,
+                        }),
+                    });
+                }
+            }
+        }
+
+        Ok(trajectory)
+    }
+
+    pub fn model(&self) -> Model {
+        self.model
+    }
+
+    pub fn directions(&self) -> &[Direction] {
+        &self.directions
+    }
+
+    /// The starting position followed by the position after every command.
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    pub fn final_position(&self) -> Position {
+        self.positions[self.positions.len() - 1]
+    }
+
+    /// Index into [`Trajectory::positions`] of the first deepest state.
+    pub fn deepest(&self) -> usize {
+        self.positions
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, position)| position.depth)
+            .map_or(0, |(idx, _)| idx)
+    }
+
+    /// One `step,command,horizontal,depth,aim` row per state. Step 0 is the start, with no command.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,command,horizontal,depth,aim\n");
+        let commands = std::iter::once(None).chain(self.directions.iter().map(Some));
+
+        for (step, (command, position)) in commands.zip(self.positions.iter()).enumerate() {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                step,
+                command.map(Direction::to_string).unwrap_or_default(),
+                position.horizontal,
+                position.depth,
+                position.aim
+            ));
+        }
+
+        csv
+    }
+
+    /// Plot of depth against horizontal distance, deeper further down. Every `reset` starts a new
+    /// line from the origin. The surface is dashed and the deepest state is marked.
+    pub fn to_svg(&self) -> String {
+        let (min_x, max_x) = bounds(self.positions.iter().map(|position| position.horizontal));
+        let (min_y, max_y) = bounds(self.positions.iter().map(|position| position.depth));
+        let plot_x = |horizontal: i64| {
+            SVG_MARGIN + fraction(horizontal, min_x, max_x) * (SVG_WIDTH - 2.0 * SVG_MARGIN)
+        };
+        let plot_y = |depth: i64| {
+            SVG_MARGIN + fraction(depth, min_y, max_y) * (SVG_HEIGHT - 2.0 * SVG_MARGIN)
+        };
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+            w = SVG_WIDTH,
+            h = SVG_HEIGHT
+        );
+        svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+        svg.push_str(&format!(
+            "<line x1=\"{:.1}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"steelblue\" stroke-dasharray=\"4 4\"/>\n",
+            plot_x(min_x),
+            plot_x(max_x),
+            y = plot_y(0)
+        ));
+
+        let mut segments: Vec<Vec<&Position>> = vec![vec![&self.positions[0]]];
+        for (direction, position) in self.directions.iter().zip(self.positions.iter().skip(1)) {
+            if *direction == Direction::Reset {
+                segments.push(vec![]);
+            }
+            segments.last_mut().unwrap().push(position);
+        }
+
+        for segment in segments.iter().filter(|segment| segment.len() > 1) {
+            let points = segment
+                .iter()
+                .map(|position| {
+                    format!(
+                        "{:.1},{:.1}",
+                        plot_x(position.horizontal),
+                        plot_y(position.depth)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            svg.push_str(&format!(
+                "<polyline fill=\"none\" stroke=\"black\" stroke-width=\"1.5\" points=\"{}\"/>\n",
+                points
+            ));
+        }
+
+        let deepest = self.positions[self.deepest()];
+        svg.push_str(&format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"4\" fill=\"red\"/>\n",
+            plot_x(deepest.horizontal),
+            plot_y(deepest.depth)
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"12\">max depth {} at {}</text>\n",
+            SVG_MARGIN,
+            SVG_HEIGHT - SVG_MARGIN / 3.0,
+            deepest.depth,
+            deepest.horizontal
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"12\">depth {}..{}, distance {}..{}</text>\n",
+            SVG_MARGIN,
+            SVG_MARGIN / 2.0,
+            min_y,
+            max_y,
+            min_x,
+            max_x
+        ));
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+}
+
+/// Range of the values including 0.
+fn bounds(values: impl Iterator<Item = i64>) -> (i64, i64) {
+    values.fold((0, 0), |(min, max), value| (min.min(value), max.max(value)))
+}
+
+/// How far `value` is from `min` towards `max`, between 0 and 1. The distances are taken in i128,
+/// as they do not always fit in i64.
+fn fraction(value: i64, min: i64, max: i64) -> f64 {
+    let range = (i128::from(max) - i128::from(min)).max(1);
+
+    (i128::from(value) - i128::from(min)) as f64 / range as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Route, Violation};
+
+    #[test]
+    fn keeps_the_states_before_a_failing_command() {
+        let route: Route = "down 2\nforward 1\nup 5\nforward 3\n".parse().unwrap();
+        let interrupted = Trajectory::record(Model::Plain, route.directions()).unwrap_err();
+
+        assert_eq!(
+            interrupted.trajectory.directions(),
+            [Direction::Down(2), Direction::Forward(1)]
+        );
+        assert_eq!(interrupted.trajectory.positions().len(), 3);
+        assert_eq!(
+            interrupted.reason,
+            Interruption::Navigation(NavigationError {
+                index: 2,
+                direction: Direction::Up(5),
+                position: interrupted.trajectory.final_position(),
+                violation: Violation::AboveSurface,
+            })
+        );
+    }
+
+    #[test]
+    fn plots_positions_across_the_whole_i64_range() {
+        let route: Route = format!(
+            "backward {max}\nbackward 1\nforward {max}\nforward {max}\ndown {max}\n",
+            max = i64::MAX
+        )
+        .parse()
+        .unwrap();
+        let trajectory = Trajectory::record(Model::Plain, route.directions()).unwrap();
+        let svg = trajectory.to_svg();
+
+        assert!(
+            svg.contains(
+                "points=\"400.0,40.0 40.0,40.0 40.0,40.0 400.0,40.0 760.0,40.0 760.0,360.0\""
+            ),
+            "{}",
+            svg
+        );
+        assert!(svg.contains(&format!(
+            "depth 0..{}, distance {}..{}",
+            i64::MAX,
+            i64::MIN,
+            i64::MAX - 1
+        )));
+
+        let flat = Trajectory::record(Model::Plain, []).unwrap().to_svg();
+        assert!(flat.contains("<circle cx=\"40.0\" cy=\"40.0\""), "{}", flat);
+    }
+
+    #[test]
+    fn stops_routes_longer_than_the_limit_without_unrolling_them() {
+        let route: Route = "repeat 10000000 { forward 1 }".parse().unwrap();
+        let interrupted = Trajectory::record(Model::Plain, route.directions()).unwrap_err();
+
+        assert_eq!(interrupted.reason, Interruption::TooLong);
+        assert_eq!(
+            interrupted.trajectory.directions().len(),
+            Trajectory::MAX_COMMANDS
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use crate::Day20;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+fn pixel(lit: bool) -> char {
+    if lit {
+        '#'
+    } else {
+        '.'
+    }
+}
+
+impl Generator for Day20 {
+    const DEFAULT_SIZE: usize = 100;
+
+    /// Random enhancement algorithm and a `size` x `size` input image. If the algorithm lights up
+    /// dark neighbourhoods, it also darkens lit ones - otherwise the lit pixel count would be infinite.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let size = size.max(1);
+        let mut algorithm = (0..512).map(|_| rng.gen_bool(0.5)).collect::<Vec<_>>();
+        if algorithm[0] {
+            algorithm[511] = false;
+        }
+
+        let mut input = algorithm.into_iter().map(pixel).collect::<String>();
+        input.push_str("\n\n");
+
+        for _ in 0..size {
+            input.extend((0..size).map(|_| pixel(rng.gen_bool(0.5))));
+            input.push('\n');
+        }
+
+        input.into()
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::Result;
+use std::str::FromStr;
+use utils::frames::{Animated, Palette, Recording};
+use utils::grid::Grid;
+use utils::parser::{blank_line, many1, parse_all, satisfy, separated_pair, try_map, Parser};
+use utils::Solution;
+
+mod generator;
+
+#[derive(Clone)]
+struct EnhancementPixel([u16; 512]);
+
+/// Image is infinite - everything outside of the stored grid has the background colour,
+/// which can flip on every enhancement if the algorithm lights up a pixel with an all-dark neighbourhood.
+#[derive(Clone)]
+pub struct TrenchMap(EnhancementPixel, Grid<bool>);
+
+fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+fn enhancement_algorithm<'a>() -> impl Parser<'a, EnhancementPixel> {
+    try_map(
+        many1(satisfy("'#' or '.'", pixel)),
+        "enhancement algorithm of exactly 512 pixels",
+        |pixels| {
+            let algorithm: [bool; 512] = pixels.try_into().ok()?;
+            Some(EnhancementPixel(algorithm.map(u16::from)))
+        },
+    )
+}
+
+impl FromStr for TrenchMap {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algorithm, input_image) = parse_all(
+            s,
+            separated_pair(
+                enhancement_algorithm(),
+                blank_line(),
+                Grid::parser("'#' or '.'", pixel),
+            ),
+        )?;
+
+        Ok(Self(algorithm, input_image.with_background(false)))
+    }
+}
+
+impl TrenchMap {
+    /// Stored part of the image, the rest of it is the background.
+    pub fn image(&self) -> &Grid<bool> {
+        &self.1
+    }
+
+    fn convolve(&self, x: isize, y: isize) -> bool {
+        let mut index = 0;
+
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let lit = self.1.at(x + dx, y + dy).copied().unwrap_or(false);
+                index = index << 1 | lit as usize;
+            }
+        }
+
+        self.0 .0[index] == 1
+    }
+
+    /// Applies the enhancement algorithm once and returns the number of lit pixels.
+    pub fn enhance(&mut self) -> usize {
+        let background = self.1.background().copied().unwrap_or(false);
+
+        let enhanced = Grid::from_fn(self.1.width() + 2, self.1.height() + 2, |x, y| {
+            self.convolve(x as isize - 1, y as isize - 1)
+        });
+        let background_index = if background { 511 } else { 0 };
+
+        self.1 = enhanced.with_background(self.0 .0[background_index] == 1);
+        self.lit_pixels()
+    }
+
+    pub fn lit_pixels(&self) -> usize {
+        self.1.iter().filter(|lit| **lit).count()
+    }
+}
+
+pub fn lit_pixels_after(map: &mut TrenchMap, steps: usize) -> usize {
+    let mut result = map.lit_pixels();
+    for _ in 0..steps {
+        result = map.enhance();
+    }
+
+    result
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = TrenchMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(map: &Self::Input) -> Result<Self::Part1> {
+        Ok(lit_pixels_after(&mut map.clone(), 2))
+    }
+
+    fn part2(map: &Self::Input) -> Result<Self::Part2> {
+        Ok(lit_pixels_after(&mut map.clone(), 50))
+    }
+}
+
+impl Animated for Day20 {
+    fn palette() -> Palette {
+        Palette::new(vec![[16, 16, 32], [255, 255, 255]])
+    }
+
+    /// Enhances the image as many times as part 2 does if `steps` are not given.
+    /// Every frame shows the same window - the area the image grows to in the last step.
+    fn record(map: &Self::Input, steps: Option<usize>) -> Result<Recording> {
+        let steps = steps.unwrap_or(50);
+        let mut map = map.clone();
+        let (width, height) = (map.1.width() + 2 * steps, map.1.height() + 2 * steps);
+        let mut recording = Recording::new();
+
+        for step in 0..=steps {
+            if step > 0 {
+                map.enhance();
+            }
+
+            // The image grows by one pixel on every side with every enhancement.
+            let margin = (steps - step) as isize;
+            let window = Grid::from_fn(width, height, |x, y| {
+                map.1
+                    .at(x as isize - margin, y as isize - margin)
+                    .copied()
+                    .unwrap_or(false)
+            });
+            recording.capture(&window, |lit| u8::from(*lit));
+        }
+
+        Ok(recording)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::Day21;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day21 {
+    const DEFAULT_SIZE: usize = 1;
+
+    /// Starting positions of both players. The input has a fixed shape, so `size` is ignored.
+    fn generate(rng: &mut StdRng, _size: usize) -> GeneratedInput {
+        format!(
+            "Player 1 starting position: {}\nPlayer 2 starting position: {}\n",
+            rng.gen_range(1..=10),
+            rng.gen_range(1..=10)
+        )
+        .into()
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::str::FromStr;
+use utils::instrument;
+use utils::parser::{
+    integer, line_ending, parse_all, preceded, separated_pair, tag, try_map, Parser,
+};
+use utils::Solution;
+
+mod generator;
+
+/// Starting positions of both players.
+pub struct GameState {
+    one_pos: u64,
+    two_pos: u64,
+}
+
+impl GameState {
+    pub fn positions(&self) -> (u64, u64) {
+        (self.one_pos, self.two_pos)
+    }
+}
+
+/// Starting position of one player, on the track of spaces 1 to 10.
+fn starting_position<'a>(player: &'static str) -> impl Parser<'a, u64> {
+    preceded(
+        tag(player),
+        preceded(
+            tag(" starting position: "),
+            try_map(integer(), "position between 1 and 10", |position: u64| {
+                (1..=10).contains(&position).then_some(position)
+            }),
+        ),
+    )
+}
+
+impl FromStr for GameState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parser = separated_pair(
+            starting_position("Player 1"),
+            line_ending(),
+            starting_position("Player 2"),
+        );
+        let (one_pos, two_pos) = parse_all(s, parser)?;
+
+        Ok(Self { one_pos, two_pos })
+    }
+}
+
+pub struct GameScore {
+    pub p1_score: u64,
+    pub p2_score: u64,
+    pub rolls: u64,
+}
+
+struct DeterministicDice {
+    value: u64,
+    rolls: u64,
+}
+
+impl DeterministicDice {
+    fn new() -> Self {
+        Self { value: 1, rolls: 0 }
+    }
+
+    fn roll(&mut self) -> u64 {
+        let val = self.value;
+
+        self.value += 1;
+        self.rolls += 1;
+
+        if self.value == 101 {
+            self.value = 1;
+        }
+
+        val
+    }
+}
+
+/// Plays with the deterministic die until one of the players reaches 1000 points.
+pub fn simulate_game(initial: &GameState) -> GameScore {
+    let mut p1_score = 0;
+    let mut p2_score = 0;
+    let mut p1_pos = initial.one_pos - 1;
+    let mut p2_pos = initial.two_pos - 1;
+    let mut p1_turn = true;
+
+    let mut dice = DeterministicDice::new();
+
+    while p1_score < 1000 && p2_score < 1000 {
+        if p1_turn {
+            p1_pos += dice.roll() + dice.roll() + dice.roll();
+            p1_pos %= 10;
+            p1_score += p1_pos + 1;
+        } else {
+            p2_pos += dice.roll() + dice.roll() + dice.roll();
+            p2_pos %= 10;
+            p2_score += p2_pos + 1;
+        }
+
+        p1_turn = !p1_turn;
+    }
+
+    GameScore {
+        p1_score,
+        p2_score,
+        rolls: dice.rolls,
+    }
+}
+
+const POSSIBLE_ROLLS: [(u64, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+// We just memoize game states aggressively.
+fn count_states(
+    p1_pos: u64,
+    p2_pos: u64,
+    p1_score: u64,
+    p2_score: u64,
+    memo: &mut HashMap<(u64, u64, u64, u64), (u64, u64)>,
+) -> (u64, u64) {
+    if let Some(score) = memo.get(&(p1_pos, p2_pos, p1_score, p2_score)) {
+        instrument::count("day21.memo_hits");
+        *score
+    } else if p1_score >= 21 {
+        (1, 0)
+    } else if p2_score >= 21 {
+        (0, 1)
+    } else {
+        instrument::count("day21.memo_misses");
+        let state = (p1_pos, p2_pos, p1_score, p2_score);
+
+        for (add, freq) in POSSIBLE_ROLLS {
+            let p1_npos = (p1_pos + add) % 10;
+            let p2_npos = p2_pos;
+            let p1_nscore = p1_score + p1_npos + 1;
+            let p2_nscore = p2_score;
+
+            let subtree = count_states(p2_npos, p1_npos, p2_nscore, p1_nscore, memo);
+            let entry = memo.entry(state).or_insert((0, 0));
+            entry.0 += freq * subtree.1;
+            entry.1 += freq * subtree.0;
+        }
+
+        *memo.get(&state).unwrap()
+    }
+}
+
+/// Number of universes in which each player wins the game with the Dirac die.
+pub fn dirac_wins(initial: &GameState) -> (u64, u64) {
+    count_states(
+        initial.one_pos - 1,
+        initial.two_pos - 1,
+        0,
+        0,
+        &mut HashMap::default(),
+    )
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = GameState;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(state: &Self::Input) -> Result<Self::Part1> {
+        let GameScore {
+            p1_score,
+            p2_score,
+            rolls,
+        } = simulate_game(state);
+
+        Ok(u64::min(p1_score, p2_score) * rolls)
+    }
+
+    fn part2(state: &Self::Input) -> Result<Self::Part2> {
+        let (p1_wins, p2_wins) = dirac_wins(state);
+
+        Ok(u64::max(p1_wins, p2_wins))
+    }
+}
@@ -0,0 +1,49 @@
+use crate::Day22;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::ops::RangeInclusive;
+use utils::generator::{GeneratedInput, Generator};
+
+/// Steps which fit in the initialization procedure region, like the first steps of the real input.
+const INITIALIZATION_STEPS: usize = 20;
+
+fn range(rng: &mut StdRng, bounds: RangeInclusive<i64>, max_len: i64) -> String {
+    let start = rng.gen_range(bounds.clone());
+    let end = rng.gen_range(start..=(start + max_len).min(*bounds.end()));
+
+    format!("{}..{}", start, end)
+}
+
+impl Generator for Day22 {
+    const DEFAULT_SIZE: usize = 420;
+
+    /// Reboot manual with `size` steps. The first steps stay within -50..50 on every axis,
+    /// the rest span the whole reactor.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let mut input = String::new();
+
+        for step in 0..size.max(1) {
+            let (bounds, max_len) = if step < INITIALIZATION_STEPS {
+                (-50..=50, 50)
+            } else {
+                (-100_000..=100_000, 40_000)
+            };
+
+            let state = if step == 0 || rng.gen_bool(0.6) {
+                "on"
+            } else {
+                "off"
+            };
+
+            input.push_str(&format!(
+                "{} x={},y={},z={}\n",
+                state,
+                range(rng, bounds.clone(), max_len),
+                range(rng, bounds.clone(), max_len),
+                range(rng, bounds, max_len)
+            ));
+        }
+
+        input.into()
+    }
+}
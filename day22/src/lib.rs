@@ -0,0 +1,208 @@
+use anyhow::Result;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use utils::instrument;
+use utils::parser::{
+    integer, map, one_of, pair, parse_all, parse_lines, preceded, separated_pair, space, tag,
+    Parser,
+};
+use utils::Solution;
+
+mod generator;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RebootInstruction {
+    On,
+    Off,
+}
+
+impl RebootInstruction {
+    fn inverse(&self) -> RebootInstruction {
+        match *self {
+            RebootInstruction::On => RebootInstruction::Off,
+            RebootInstruction::Off => RebootInstruction::On,
+        }
+    }
+}
+
+/// Reboot step - turns every cube of the cuboid on or off. Ranges are inclusive.
+#[derive(Debug, Clone)]
+pub struct RebootCuboid {
+    x0: isize,
+    x1: isize,
+    y0: isize,
+    y1: isize,
+    z0: isize,
+    z1: isize,
+    instruction: RebootInstruction,
+}
+
+impl RebootCuboid {
+    pub fn instruction(&self) -> RebootInstruction {
+        self.instruction
+    }
+
+    pub fn x(&self) -> RangeInclusive<isize> {
+        self.x0..=self.x1
+    }
+
+    pub fn y(&self) -> RangeInclusive<isize> {
+        self.y0..=self.y1
+    }
+
+    pub fn z(&self) -> RangeInclusive<isize> {
+        self.z0..=self.z1
+    }
+
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        let x0 = isize::max(self.x0, other.x0);
+        let x1 = isize::min(self.x1, other.x1);
+        let y0 = isize::max(self.y0, other.y0);
+        let y1 = isize::min(self.y1, other.y1);
+        let z0 = isize::max(self.z0, other.z0);
+        let z1 = isize::min(self.z1, other.z1);
+
+        if x0 > x1 || y0 > y1 || z0 > z1 {
+            None
+        } else {
+            Some(Self {
+                x0,
+                x1,
+                y0,
+                y1,
+                z0,
+                z1,
+                instruction: self.instruction.inverse(),
+            })
+        }
+    }
+
+    fn in_limit(&self, limit: &Option<(isize, isize)>) -> bool {
+        if let Some((min, max)) = limit.iter().copied().next() {
+            (min..=max).contains(&self.x0)
+                && (min..=max).contains(&self.x1)
+                && (min..=max).contains(&self.y0)
+                && (min..=max).contains(&self.y1)
+                && (min..=max).contains(&self.z0)
+                && (min..=max).contains(&self.z1)
+        } else {
+            true
+        }
+    }
+
+    fn area(&self) -> isize {
+        let result = (self.x1 - self.x0 + 1) * (self.y1 - self.y0 + 1) * (self.z1 - self.z0 + 1);
+
+        match self.instruction {
+            RebootInstruction::Off => -result,
+            RebootInstruction::On => result,
+        }
+    }
+}
+
+fn reboot_cuboid<'a>() -> impl Parser<'a, RebootCuboid> {
+    let instruction = one_of([
+        ("on", RebootInstruction::On),
+        ("off", RebootInstruction::Off),
+    ]);
+    let range = || separated_pair(integer(), tag(".."), integer());
+    let cuboid = pair(
+        pair(preceded(tag("x="), range()), preceded(tag(",y="), range())),
+        preceded(tag(",z="), range()),
+    );
+
+    map(
+        separated_pair(instruction, space(), cuboid),
+        |(instruction, (((x0, x1), (y0, y1)), (z0, z1)))| RebootCuboid {
+            x0,
+            x1,
+            y0,
+            y1,
+            z0,
+            z1,
+            instruction,
+        },
+    )
+}
+
+impl FromStr for RebootCuboid {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(s, reboot_cuboid())?)
+    }
+}
+
+impl RebootManual {
+    pub fn steps(&self) -> &[RebootCuboid] {
+        &self.0
+    }
+
+    /// Number of cubes left on after all steps. With a `(min, max)` limit only the steps
+    /// fully inside the region `min..=max` on every axis are performed.
+    pub fn on_cubes_count(&self, limit: Option<(isize, isize)>) -> isize {
+        if limit.is_some() {
+            let limit_cuboids: Vec<_> = self
+                .0
+                .iter()
+                .filter(|&c| c.in_limit(&limit))
+                .cloned()
+                .collect();
+
+            return Self(limit_cuboids).on_cubes_count(None);
+        }
+
+        let mut matching_cubes: Vec<RebootCuboid> = vec![];
+
+        for cube in self.0.iter() {
+            let mut new_intersects = vec![];
+            for other in matching_cubes.iter() {
+                if let Some(c) = other.intersect(cube) {
+                    new_intersects.push(c);
+                }
+            }
+
+            instrument::add("day22.intersections", new_intersects.len() as u64);
+
+            if let RebootInstruction::On = cube.instruction {
+                matching_cubes.push(cube.clone());
+            }
+
+            matching_cubes.extend(new_intersects);
+        }
+
+        instrument::maximum("day22.signed_cuboids", matching_cubes.len() as u64);
+        matching_cubes.iter().map(RebootCuboid::area).sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct RebootManual(Vec<RebootCuboid>);
+
+impl FromStr for RebootManual {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse_lines(s, reboot_cuboid())?))
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = RebootManual;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(reboot_manual: &Self::Input) -> Result<Self::Part1> {
+        Ok(reboot_manual.on_cubes_count(Some((-50, 50))))
+    }
+
+    fn part2(reboot_manual: &Self::Input) -> Result<Self::Part2> {
+        Ok(reboot_manual.on_cubes_count(None))
+    }
+}
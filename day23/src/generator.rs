@@ -0,0 +1,23 @@
+use crate::Day23;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day23 {
+    const DEFAULT_SIZE: usize = 1;
+
+    /// Burrow with two amphipods of every type shuffled between the side rooms.
+    /// The input has a fixed shape, so `size` is ignored.
+    fn generate(rng: &mut StdRng, _size: usize) -> GeneratedInput {
+        let mut amphipods = *b"AABBCCDD";
+        amphipods.shuffle(rng);
+
+        let [a, b, c, d, e, f, g, h] = amphipods.map(char::from);
+
+        format!(
+            "#############\n#...........#\n###{}#{}#{}#{}###\n  #{}#{}#{}#{}#\n  #########\n",
+            a, b, c, d, e, f, g, h
+        )
+        .into()
+    }
+}
@@ -0,0 +1,277 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+use utils::instrument;
+use utils::parser::{
+    delimited, line_ending, many1, map, pair, parse_all, preceded, satisfy, tag, terminated,
+    try_map, Input, Parser,
+};
+use utils::search::{astar, SearchProblem};
+use utils::Solution;
+
+mod generator;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
+pub struct State<const N: usize> {
+    // indexes 0-10 = corridor. 2, 4, 6, 8 unused (rule - room entrances).
+    // indexes >= 10 are rooms.
+    // values: 1 - A, 2 - B, 3 - C, 4 - D.
+    data: [u8; N],
+}
+
+fn amphipod(c: char) -> Option<u8> {
+    ('A'..='D').contains(&c).then(|| c as u8 - b'A' + 1)
+}
+
+fn side_rooms<'a>() -> impl Parser<'a, [u8; 4]> {
+    let amphipod = || satisfy("amphipod (A-D)", amphipod);
+    let next_amphipod = || preceded(tag("#"), amphipod());
+
+    map(
+        pair(
+            pair(amphipod(), next_amphipod()),
+            pair(next_amphipod(), next_amphipod()),
+        ),
+        |((a, b), (c, d))| [a, b, c, d],
+    )
+}
+
+impl<const N: usize> State<N> {
+    /// Burrow diagram with as many rows of side rooms as the state is deep.
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        move |input: Input<'a>| {
+            let space = satisfy("'.' or amphipod (A-D)", |c| match c {
+                '.' => Some(0),
+                c => amphipod(c),
+            });
+            let hallway = try_map(many1(space), "hallway of 11 spaces", |spaces| {
+                <[u8; 11]>::try_from(spaces).ok()
+            });
+
+            let (_, input) = pair(tag("#############"), line_ending()).parse(input)?;
+            let (hallway, mut input) =
+                terminated(delimited(tag("#"), hallway, tag("#")), line_ending()).parse(input)?;
+
+            let mut data = [0; N];
+            data[..11].copy_from_slice(&hallway);
+
+            for depth in 0..Self::ROOM_DEPTH {
+                let (prefix, suffix) = if depth == 0 {
+                    ("###", "###")
+                } else {
+                    ("  #", "#")
+                };
+
+                let (rooms, rest) = terminated(
+                    delimited(tag(prefix), side_rooms(), tag(suffix)),
+                    line_ending(),
+                )
+                .parse(input)?;
+
+                for (room, amphipod) in rooms.into_iter().enumerate() {
+                    data[11 + Self::ROOM_DEPTH * room + depth] = amphipod;
+                }
+                input = rest;
+            }
+
+            let (_, input) = tag("  #########").parse(input)?;
+
+            Ok((Self { data }, input))
+        }
+    }
+}
+
+impl<const N: usize> FromStr for State<N> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(s, Self::parser())?)
+    }
+}
+
+impl<const N: usize> State<N> {
+    const ROOM_DEPTH: usize = (N - 11) / 4;
+    const VALID_HALLWAY_IDX: &'static [usize] = &[0, 1, 3, 5, 7, 9, 10];
+    const COSTS: [u64; 4] = [1, 10, 100, 1000];
+
+    fn apply(&self, step: (usize, usize, usize)) -> (Self, u64) {
+        let cost = Self::COSTS[self.data[step.0] as usize - 1] * step.2 as u64;
+
+        let mut new_data = self.data;
+        new_data.swap(step.0, step.1);
+
+        (Self { data: new_data }, cost)
+    }
+
+    /// Whether every amphipod is in its own room.
+    pub fn is_complete(&self) -> bool {
+        for room in 0..4 {
+            let room_data =
+                &self.data[11 + room * Self::ROOM_DEPTH..11 + (room + 1) * Self::ROOM_DEPTH];
+
+            if !room_data.iter().all(|v| *v == room as u8 + 1) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn next_moves(&self) -> impl Iterator<Item = (usize, usize, usize)> {
+        let mut moves = vec![];
+
+        let room_first_idx = |room_idx| 11 + room_idx * Self::ROOM_DEPTH;
+        let rooms_data = [0, 1, 2, 3].map(|i| &self.data[room_first_idx(i)..room_first_idx(i + 1)]);
+        let room_depth_occupied = rooms_data.map(|room| room.iter().position(|s| *s > 0));
+
+        // Generate from hallway to room moves.
+        for valid_idx in Self::VALID_HALLWAY_IDX.iter().copied() {
+            if self.data[valid_idx] > 0 {
+                let valid_room = self.data[valid_idx] - 1;
+                let valid_room_entrance = 2 + 2 * valid_room;
+                let corridor_path = u8::min(valid_idx as u8, valid_room_entrance)
+                    ..=u8::max(valid_idx as u8, valid_room_entrance);
+
+                // We can cross through corridor without bouncing another amphipod.
+                let path_unobstructed = corridor_path
+                    .clone()
+                    .all(|i| i as usize == valid_idx || self.data[i as usize] == 0);
+                // "Move after corridor" rule is fulfilled (amphipod stopped in the corridor moves if and only if it can move to the room and room is already valid.)
+                let room_ready = rooms_data[valid_room as usize]
+                    .iter()
+                    .copied()
+                    .all(|pod| pod == 0 || pod == self.data[valid_idx]);
+
+                if path_unobstructed && room_ready {
+                    // Move amphipod immediately to maximum depth of the room.
+                    let depth_to_move =
+                        room_depth_occupied[valid_room as usize].unwrap_or(Self::ROOM_DEPTH) - 1;
+
+                    moves.push((
+                        valid_idx,
+                        room_first_idx(valid_room as usize) + depth_to_move,
+                        corridor_path.count() + depth_to_move, // we omit 1 here because we overshoot by 1 in corridor_path count!
+                    ));
+                }
+            }
+        }
+
+        // Generate from room to hallway moves.
+        for (room_idx, _) in room_depth_occupied.iter().enumerate() {
+            if let Some(room_pod_depth) = room_depth_occupied[room_idx] {
+                let pod = self.data[room_first_idx(room_idx) + room_pod_depth];
+                let target_room = pod - 1;
+
+                let direct_route_range = u8::min(2 + room_idx as u8 * 2, 2 + target_room * 2)
+                    ..=u8::max(2 + room_idx as u8 * 2, 2 + target_room * 2);
+
+                for valid_hallway_idx in Self::VALID_HALLWAY_IDX.iter().copied() {
+                    let on_direct_route = direct_route_range.contains(&(valid_hallway_idx as u8));
+                    let corridor_path = u8::min(valid_hallway_idx as u8, 2 + room_idx as u8 * 2)
+                        ..=u8::max(valid_hallway_idx as u8, 2 + room_idx as u8 * 2);
+                    let path_unobstructed =
+                        corridor_path.clone().all(|i| self.data[i as usize] == 0);
+
+                    // We don't want to make a move where we go to immediate room entrance if we happen to go further.
+                    if !(on_direct_route && corridor_path.clone().count() > 2) && path_unobstructed
+                    {
+                        moves.push((
+                            room_first_idx(room_idx) + room_pod_depth,
+                            valid_hallway_idx,
+                            corridor_path.count() + room_pod_depth,
+                        ));
+                    }
+                }
+            }
+        }
+
+        moves.into_iter()
+    }
+}
+
+/// Organizing the amphipods from the given burrow, every move costs its energy.
+struct Organizing<const N: usize>(State<N>);
+
+impl<const N: usize> SearchProblem for Organizing<N> {
+    type State = State<N>;
+    type Cost = u64;
+
+    fn start(&self) -> Self::State {
+        self.0
+    }
+
+    fn is_goal(&self, state: &Self::State) -> bool {
+        state.is_complete()
+    }
+
+    fn successors(&self, state: &Self::State) -> impl Iterator<Item = (Self::State, u64)> {
+        state.next_moves().map(|step| state.apply(step))
+    }
+
+    /// Energy needed to walk every amphipod which is not in its own room to that room's topmost space,
+    /// as if no other amphipod was in the way.
+    fn heuristic(&self, state: &Self::State) -> u64 {
+        let entrance = |room: usize| 2 + 2 * room;
+
+        state
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, pod)| **pod > 0)
+            .map(|(idx, pod)| {
+                let target_room = *pod as usize - 1;
+                let steps = if idx < 11 {
+                    idx.abs_diff(entrance(target_room)) + 1
+                } else {
+                    let room = (idx - 11) / State::<N>::ROOM_DEPTH;
+                    let depth = (idx - 11) % State::<N>::ROOM_DEPTH;
+
+                    if room == target_room {
+                        0
+                    } else {
+                        depth + 1 + entrance(room).abs_diff(entrance(target_room)) + 1
+                    }
+                };
+
+                State::<N>::COSTS[target_room] * steps as u64
+            })
+            .sum()
+    }
+}
+
+/// Least energy required to organize the amphipods, if they can be organized at all.
+pub fn organizing_cost<const N: usize>(initial: State<N>) -> Option<u64> {
+    let _span = instrument::span("day23.organizing_cost");
+    astar(&Organizing(initial)).map(|path| path.cost)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    /// Folded burrow from the input and the burrow after unfolding the diagram for part 2.
+    type Input = (State<{ 11 + 2 * 4 }>, State<{ 11 + 4 * 4 }>);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let state = input.parse()?;
+
+        let mut part2_input = input.lines().collect::<Vec<_>>();
+        if part2_input.len() < 3 {
+            return Err(anyhow!("burrow diagram is too short to unfold"));
+        }
+        part2_input.splice(3..3, ["  #D#C#B#A#", "  #D#B#A#C#"]);
+
+        let state_part2 = part2_input.join("\n").parse()?;
+
+        Ok((state, state_part2))
+    }
+
+    fn part1((state, _): &Self::Input) -> Result<Self::Part1> {
+        organizing_cost(*state).ok_or_else(|| anyhow!("couldn't find solution for given data"))
+    }
+
+    fn part2((_, state_part2): &Self::Input) -> Result<Self::Part2> {
+        organizing_cost(*state_part2)
+            .ok_or_else(|| anyhow!("couldn't find solution for given data (after unfolding)"))
+    }
+}
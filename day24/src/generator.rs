@@ -0,0 +1,68 @@
+use crate::Day24;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fmt::Write;
+use utils::generator::{GeneratedInput, Generator};
+
+const DIGITS: usize = 14;
+
+/// Block checking a single digit of the model number. Every MONAD is made of 14 of them,
+/// differing only in the `div z` operand and two constants.
+fn digit_check(divisor: i64, check: i64, offset: i64) -> String {
+    format!(
+        "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+         mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y\n",
+        divisor, check, offset
+    )
+}
+
+impl Generator for Day24 {
+    const DEFAULT_SIZE: usize = DIGITS;
+
+    /// MONAD built from the same digit-checking blocks as the real one: half of them push
+    /// a digit onto a base-26 stack in `z`, the other half pop it and require a fixed difference
+    /// between the two digits. The input has a fixed shape, so `size` is ignored.
+    /// Ground truth lists the largest and smallest accepted model numbers.
+    fn generate(rng: &mut StdRng, _size: usize) -> GeneratedInput {
+        let mut input = String::new();
+        let mut largest = [0; DIGITS];
+        let mut smallest = [0; DIGITS];
+        let mut stack: Vec<(usize, i64)> = vec![];
+        let mut pushes_left = DIGITS / 2;
+
+        for digit in 0..DIGITS {
+            let push = pushes_left > 0 && (stack.is_empty() || rng.gen_bool(0.5));
+
+            if push {
+                pushes_left -= 1;
+                // Checks above 9 can never match a digit, so the block always pushes.
+                let offset = rng.gen_range(1..=16);
+                input.push_str(&digit_check(1, rng.gen_range(10..=15), offset));
+                stack.push((digit, offset));
+            } else if let Some((pushed, offset)) = stack.pop() {
+                // Accepted only when `digit == pushed + difference`.
+                let difference: i64 = rng.gen_range(-8..=8);
+                input.push_str(&digit_check(26, difference - offset, rng.gen_range(1..=16)));
+
+                largest[pushed] = 9.min(9 - difference);
+                largest[digit] = largest[pushed] + difference;
+                smallest[pushed] = 1.max(1 - difference);
+                smallest[digit] = smallest[pushed] + difference;
+            }
+        }
+
+        let mut ground_truth = String::new();
+        for (part, digits) in [(1, largest), (2, smallest)] {
+            let number = digits
+                .iter()
+                .map(|digit| digit.to_string())
+                .collect::<String>();
+            let _ = writeln!(ground_truth, "part {}: {}", part, number);
+        }
+
+        GeneratedInput {
+            input,
+            ground_truth: Some(ground_truth),
+        }
+    }
+}
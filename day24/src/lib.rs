@@ -0,0 +1,461 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+use utils::instrument;
+use utils::parser::{
+    integer, map, one_of, or, pair, parse_all, parse_lines, preceded, separated_pair, space, tag,
+    terminated, Parser,
+};
+use utils::Solution;
+
+mod generator;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    W,
+    X,
+    Y,
+    Z,
+    C(i64),
+}
+
+/// Registers of the ALU.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Memory {
+    pub w: i64,
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Memory {
+    /// Register to store a result in. Instructions are public, so a constant can end up
+    /// as a destination.
+    fn reg(&mut self, operand: &Operand) -> Result<&mut i64> {
+        use Operand::*;
+
+        match operand {
+            X => Ok(&mut self.x),
+            Y => Ok(&mut self.y),
+            Z => Ok(&mut self.z),
+            W => Ok(&mut self.w),
+            C(i) => Err(anyhow!("constant {} cannot store a result", i)),
+        }
+    }
+
+    fn value(&self, operand: &Operand) -> i64 {
+        use Operand::*;
+
+        match operand {
+            X => self.x,
+            Y => self.y,
+            Z => self.z,
+            W => self.w,
+            C(i) => *i,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Inp(Operand),
+    Add(Operand, Operand),
+    Mul(Operand, Operand),
+    Div(Operand, Operand),
+    Mod(Operand, Operand),
+    Eql(Operand, Operand),
+}
+
+pub struct Program(Vec<Operation>);
+
+impl Program {
+    pub fn operations(&self) -> &[Operation] {
+        &self.0
+    }
+}
+
+fn register<'a>() -> impl Parser<'a, Operand> {
+    one_of([
+        ("w", Operand::W),
+        ("x", Operand::X),
+        ("y", Operand::Y),
+        ("z", Operand::Z),
+    ])
+}
+
+fn operation<'a>() -> impl Parser<'a, Operation> {
+    let input = map(
+        preceded(pair(tag("inp"), space()), register()),
+        Operation::Inp,
+    );
+
+    let binary = one_of([
+        ("add", Operation::Add as fn(Operand, Operand) -> Operation),
+        ("mul", Operation::Mul),
+        ("div", Operation::Div),
+        ("mod", Operation::Mod),
+        ("eql", Operation::Eql),
+    ]);
+    let operand = or(register(), map(integer(), Operand::C));
+    let binary = map(
+        pair(
+            terminated(binary, space()),
+            separated_pair(register(), space(), operand),
+        ),
+        |(operation, (a, b))| operation(a, b),
+    );
+
+    or(input, binary)
+}
+
+impl FromStr for Operation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(s, operation())?)
+    }
+}
+
+impl FromStr for Program {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse_lines(s, operation())?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArithmeticLogicUnit;
+impl ArithmeticLogicUnit {
+    /// Runs the program from zeroed registers, feeding `inp` instructions from `input`.
+    /// Fails on division or modulo by zero, modulo of negative numbers, arithmetic overflow,
+    /// when the program reads past the end of the input and when an instruction stores its
+    /// result in a constant.
+    pub fn execute(
+        &self,
+        program: &Program,
+        input: impl IntoIterator<Item = i64>,
+    ) -> Result<Memory> {
+        let mut tape = input.into_iter();
+        let mut mem = Memory::default();
+
+        for op in program.0.iter() {
+            use Operation::*;
+
+            match op {
+                Add(a, b) => {
+                    let b_val = mem.value(b);
+                    let a_val = mem.reg(a)?;
+                    *a_val = a_val
+                        .checked_add(b_val)
+                        .ok_or_else(|| anyhow!("{} + {} overflows", a_val, b_val))?;
+                }
+                Mul(a, b) => {
+                    let b_val = mem.value(b);
+                    let a_val = mem.reg(a)?;
+                    *a_val = a_val
+                        .checked_mul(b_val)
+                        .ok_or_else(|| anyhow!("{} * {} overflows", a_val, b_val))?;
+                }
+                Mod(a, b) => {
+                    let b_val = mem.value(b);
+                    let a_val = mem.reg(a)?;
+                    if b_val == 0 {
+                        return Err(anyhow!("modulo by zero"));
+                    }
+
+                    if *a_val < 0 || b_val < 0 {
+                        return Err(anyhow!("modulo of {} by {} is undefined", a_val, b_val));
+                    }
+
+                    *a_val = a_val
+                        .checked_rem(b_val)
+                        .ok_or_else(|| anyhow!("{} mod {} overflows", a_val, b_val))?;
+                }
+                Div(a, b) => {
+                    let b_val = mem.value(b);
+                    let a_val = mem.reg(a)?;
+                    if b_val == 0 {
+                        return Err(anyhow!("division by zero"));
+                    }
+
+                    *a_val = a_val
+                        .checked_div(b_val)
+                        .ok_or_else(|| anyhow!("{} / {} overflows", a_val, b_val))?;
+                }
+                Eql(a, b) => {
+                    let a_val = mem.value(a);
+                    let b_val = mem.value(b);
+
+                    if a_val == b_val {
+                        *mem.reg(a)? = 1;
+                    } else {
+                        *mem.reg(a)? = 0;
+                    }
+                }
+                Inp(a) => {
+                    *mem.reg(a)? = tape
+                        .next()
+                        .ok_or_else(|| anyhow!("program ran out of input"))?;
+                }
+            }
+        }
+
+        Ok(mem)
+    }
+}
+
+/// Constants of the block checking a single digit of the model number. A MONAD is 14 of these
+/// blocks, identical apart from the `div z`, `add x` and second `add y` operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DigitCheck {
+    divisor: i64,
+    check: i64,
+    offset: i64,
+}
+
+const BLOCK_LEN: usize = 18;
+
+impl DigitCheck {
+    fn block(&self) -> [Operation; BLOCK_LEN] {
+        use Operand::*;
+        use Operation::*;
+
+        [
+            Inp(W),
+            Mul(X, C(0)),
+            Add(X, Z),
+            Mod(X, C(26)),
+            Div(Z, C(self.divisor)),
+            Add(X, C(self.check)),
+            Eql(X, W),
+            Eql(X, C(0)),
+            Mul(Y, C(0)),
+            Add(Y, C(25)),
+            Mul(Y, X),
+            Add(Y, C(1)),
+            Mul(Z, Y),
+            Mul(Y, C(0)),
+            Add(Y, W),
+            Add(Y, C(self.offset)),
+            Mul(Y, X),
+            Add(Z, Y),
+        ]
+    }
+
+    /// `z` after the block reads the digit `w`, and whether the digit passed the check. `None` if
+    /// the block overflows or takes the modulo of a negative `z`, which the ALU rejects as well.
+    fn step(&self, z: i64, w: i64) -> Option<(i64, bool)> {
+        if z < 0 {
+            return None;
+        }
+
+        let matched = (z % 26).checked_add(self.check)? == w;
+        let z = if self.pops() { z / 26 } else { z };
+
+        if matched {
+            Some((z, true))
+        } else {
+            let z = z
+                .checked_mul(26)?
+                .checked_add(w)?
+                .checked_add(self.offset)?;
+            Some((z, false))
+        }
+    }
+
+    /// Whether the block can drop a digit from `z` - only blocks dividing `z` by 26 do.
+    fn pops(&self) -> bool {
+        self.divisor == 26
+    }
+}
+
+/// Digit checks of a MONAD, with the number of blocks able to drop a digit from `z`
+/// at or after every position.
+struct Monad {
+    checks: [DigitCheck; 14],
+    remaining_drops: [usize; 14],
+}
+
+impl Monad {
+    fn new(checks: [DigitCheck; 14]) -> Self {
+        let mut remaining_drops = [0; 14];
+        for digit in (0..14).rev() {
+            let later = remaining_drops.get(digit + 1).copied().unwrap_or(0);
+            remaining_drops[digit] = later + checks[digit].pops() as usize;
+        }
+
+        Self {
+            checks,
+            remaining_drops,
+        }
+    }
+}
+
+/// Reads the constants of every digit check, failing if the program is not made of exactly
+/// 14 such blocks.
+fn digit_checks(prog: &Program) -> Result<[DigitCheck; 14]> {
+    let operations = prog.operations();
+    if operations.len() != 14 * BLOCK_LEN {
+        return Err(anyhow!(
+            "expected a MONAD of {} instructions, found {}",
+            14 * BLOCK_LEN,
+            operations.len()
+        ));
+    }
+
+    let mut checks = [DigitCheck {
+        divisor: 1,
+        check: 0,
+        offset: 0,
+    }; 14];
+
+    for (digit, block) in operations.chunks(BLOCK_LEN).enumerate() {
+        let constant = |index: usize| match block[index] {
+            Operation::Div(_, Operand::C(c)) | Operation::Add(_, Operand::C(c)) => Some(c),
+            _ => None,
+        };
+
+        let check = constant(4)
+            .zip(constant(5))
+            .zip(constant(15))
+            .map(|((divisor, check), offset)| DigitCheck {
+                divisor,
+                check,
+                offset,
+            })
+            .filter(|check| matches!(check.divisor, 1 | 26) && check.block() == block)
+            .ok_or_else(|| anyhow!("block of digit {} is not a MONAD digit check", digit + 1))?;
+
+        checks[digit] = check;
+    }
+
+    Ok(checks)
+}
+
+/// This solution exploits the fact that the input data is very structured & unique.
+// Basically for every digit there are following two programs used to calculate result:
+// prog a: if (z % 26 + px == w) { z } else { 26 * z + py + w }
+// prog b: if (z % 26 + px == w) { z / 26 } else { 26 * (z / 26) + py + w }
+// prog a is used for digits with `div z 1`, prog b for digits with `div z 26`.
+// Only z register 'live' between reads of digits to w. So basically we can do depth-first
+// search of solution space, trying digits and applying prog a/b logic accordingly.
+// key optimisation here to avoid 10**14 search is seeing that only program b can reduce z result
+// and only else-branch of prog a can significantly increase z result. We keep track of remaining
+// possibilities of 'reducing' the result through program b and how many times we've significantly
+// increased the result and bail out early if we cannot reduce the result (number of remaining reductions
+// is higher than number of increases we did).
+fn search_solution_space(
+    monad: &Monad,
+    depth: usize,
+    bad_branches: usize,
+    search_order: &[i64],
+    z: i64,
+    w: i64,
+    path: &mut [i64; 14],
+) -> bool {
+    instrument::count("day24.search_calls");
+    instrument::maximum("day24.recursion_depth", depth as u64);
+
+    if depth == 15 {
+        return z == 0;
+    } else if bad_branches > monad.remaining_drops[depth - 1] {
+        instrument::count("day24.pruned_branches");
+        return false;
+    } else {
+        let digit = monad.checks[depth - 1];
+        let Some((z, matched)) = digit.step(z, w) else {
+            instrument::count("day24.overflowed_branches");
+            return false;
+        };
+        let bad_branches = match (matched, digit.pops()) {
+            (true, true) => bad_branches.saturating_sub(1),
+            (false, false) => bad_branches + 1,
+            _ => bad_branches,
+        };
+
+        path[depth - 1] = w;
+        for new_w in search_order {
+            if search_solution_space(
+                monad,
+                depth + 1,
+                bad_branches,
+                search_order,
+                z,
+                *new_w,
+                path,
+            ) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+fn model_number(prog: &Program, search_order: &[i64; 9]) -> Result<u64> {
+    let monad = Monad::new(digit_checks(prog)?);
+    let mut path: [i64; 14] = [0; 14];
+    let alu = ArithmeticLogicUnit;
+
+    let found = search_order
+        .iter()
+        .copied()
+        .any(|w| search_solution_space(&monad, 1, 0, search_order, 0, w, &mut path));
+
+    if !found {
+        return Err(anyhow!("failed to find valid model number"));
+    }
+
+    let memory = alu.execute(prog, path)?;
+
+    if memory.z != 0 {
+        return Err(anyhow!(
+            "MONAD rejected model number {:?}: {:?}",
+            path,
+            memory
+        ));
+    }
+
+    Ok(path
+        .iter()
+        .fold(0, |number, digit| number * 10 + *digit as u64))
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Program;
+    type Part1 = u64;
+    type Part2 = u64;
+    // The model number search used to ignore the program and use the constants of one input.
+    const VERSION: u32 = 2;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(prog: &Self::Input) -> Result<Self::Part1> {
+        model_number(prog, &[9, 8, 7, 6, 5, 4, 3, 2, 1])
+    }
+
+    fn part2(prog: &Self::Input) -> Result<Self::Part2> {
+        model_number(prog, &[1, 2, 3, 4, 5, 6, 7, 8, 9])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input");
+
+    #[test]
+    fn overflowing_digit_checks_end_the_search() {
+        let mut lines = INPUT.lines().collect::<Vec<_>>();
+        lines[15] = "add y 9223372036854775000";
+        let program: Program = lines.join("\n").parse().unwrap();
+
+        let error = Day24::part1(&program).unwrap_err();
+        assert_eq!(error.to_string(), "failed to find valid model number");
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{CucumberMap, Day25, Field};
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+use utils::grid::Grid;
+
+/// Herds which keep moving for longer than this are rerolled, so the solver always terminates quickly.
+const MAX_STEPS: usize = 10_000;
+
+impl Generator for Day25 {
+    const DEFAULT_SIZE: usize = 137;
+
+    /// Sea floor with `size` rows and two columns more, like the real input,
+    /// filled with random cucumbers and guaranteed to stop moving.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let height = size.max(1);
+        let width = height + 2;
+
+        loop {
+            let fields = Grid::from_fn(width, height, |_, _| match rng.gen_range(0..3) {
+                0 => Field::Empty,
+                1 => Field::East,
+                _ => Field::South,
+            });
+            let cucumber_map = CucumberMap { fields };
+
+            let mut moving = cucumber_map.clone();
+            if (0..MAX_STEPS).any(|_| moving.step() == 0) {
+                return cucumber_map.to_string().into();
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use anyhow::Result;
+use std::{fmt::Display, str::FromStr};
+use utils::frames::{Animated, Palette, Recording};
+use utils::grid::Grid;
+use utils::Solution;
+
+mod generator;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Empty,
+    East,
+    South,
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Field::Empty => '.',
+            Field::East => '>',
+            Field::South => 'v',
+        };
+
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Sea floor is a torus - cucumbers leaving one edge reappear on the opposite one.
+#[derive(Clone, Debug)]
+pub struct CucumberMap {
+    fields: Grid<Field>,
+}
+
+impl CucumberMap {
+    fn perform_step(&mut self, herd: Field, direction: (isize, isize)) -> usize {
+        let moving = self
+            .fields
+            .positions()
+            .filter(|&position| self.fields[position] == herd)
+            .map(|position| (position, self.fields.wrapping_offset(position, direction)))
+            .filter(|&(_, next_pos)| self.fields[next_pos] == Field::Empty)
+            .collect::<Vec<_>>();
+
+        for &(position, next_pos) in moving.iter() {
+            self.fields[position] = Field::Empty;
+            self.fields[next_pos] = herd;
+        }
+
+        moving.len()
+    }
+
+    /// Moves the east-facing herd, then the south-facing one. Returns the number of sea cucumbers which moved.
+    pub fn step(&mut self) -> usize {
+        self.perform_step(Field::East, (1, 0)) + self.perform_step(Field::South, (0, 1))
+    }
+}
+
+impl Display for CucumberMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.fields)
+    }
+}
+
+impl FromStr for CucumberMap {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = Grid::parse(s, "'.', '>' or 'v'", |field| match field {
+            '.' => Some(Field::Empty),
+            '>' => Some(Field::East),
+            'v' => Some(Field::South),
+            _ => None,
+        })?;
+
+        Ok(Self { fields })
+    }
+}
+
+/// Number of the first step on which no sea cucumber moves.
+pub fn steps_to_stop(cucumber_map: &CucumberMap) -> usize {
+    let mut cucumber_map = cucumber_map.clone();
+    let mut steps = 0;
+
+    loop {
+        steps += 1;
+        if cucumber_map.step() == 0 {
+            break;
+        }
+    }
+
+    steps
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = CucumberMap;
+    type Part1 = usize;
+    type Part2 = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(cucumber_map: &Self::Input) -> Result<Self::Part1> {
+        Ok(steps_to_stop(cucumber_map))
+    }
+
+    /// There is no second puzzle on the last day - the star is awarded for completing all the others.
+    fn part2(_: &Self::Input) -> Result<Self::Part2> {
+        Ok("Merry Christmas!")
+    }
+}
+
+impl Animated for Day25 {
+    /// Sea floor is dark blue, east-facing herd is orange and south-facing herd is green.
+    fn palette() -> Palette {
+        Palette::new(vec![[0, 32, 64], [255, 136, 0], [0, 200, 80]])
+    }
+
+    /// Runs until the cucumbers stop moving if `steps` are not given.
+    fn record(cucumber_map: &Self::Input, steps: Option<usize>) -> Result<Recording> {
+        let mut cucumber_map = cucumber_map.clone();
+        let mut recording = Recording::new();
+        let shade = |field: &Field| match field {
+            Field::Empty => 0,
+            Field::East => 1,
+            Field::South => 2,
+        };
+        recording.capture(&cucumber_map.fields, shade);
+
+        for _ in 0..steps.unwrap_or(usize::MAX) {
+            let moved = cucumber_map.step();
+            recording.capture(&cucumber_map.fields, shade);
+
+            if steps.is_none() && moved == 0 {
+                break;
+            }
+        }
+
+        Ok(recording)
+    }
+}
@@ -0,0 +1,121 @@
+//! Compares ways of finding both life support ratings on large generated reports:
+//!
+//! ```text
+//! cargo bench -p day3 [-- <rows>...]
+//! ```
+//!
+//! - `in place` is the original solver: two partitioning searches over one mutable copy of the
+//!   report, the second running on the rows the first one reordered.
+//! - `two filters` runs the filter engine once per rating.
+//! - `single pass` is [`day3::life_support_ratings`], finding both with a three-way partition.
+
+use day3::{filter_rows, life_support_ratings, BitMatrix, Day3, Majority, Minority};
+use std::env;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use utils::generator;
+
+const RUNS: usize = 5;
+const SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+fn search_in_place(report: &mut BitMatrix, inverse: bool) -> Vec<bool> {
+    let mut search_set = report.height();
+    for column in 0..report.width() {
+        if search_set == 1 {
+            break;
+        }
+
+        let ones_count = report.count_ones(column, 0..search_set);
+        let zeros_count = search_set - ones_count;
+        let condition = if inverse {
+            ones_count > 0 && (ones_count < zeros_count || zeros_count == 0)
+        } else {
+            ones_count >= zeros_count
+        };
+
+        search_set = report.partition_rows(0..search_set, |row| row[column] == condition);
+    }
+
+    report.row(0).iter().by_vals().collect()
+}
+
+fn in_place(report: &BitMatrix) -> (Vec<bool>, Vec<bool>) {
+    let mut report = report.clone();
+    let oxygen = search_in_place(&mut report, false);
+    let co2 = search_in_place(&mut report, true);
+
+    (oxygen, co2)
+}
+
+fn two_filters(report: &BitMatrix) -> (Vec<bool>, Vec<bool>) {
+    let oxygen = filter_rows(report, &Majority { ties: true }).survivors[0];
+    let co2 = filter_rows(report, &Minority { ties: false }).survivors[0];
+
+    (
+        report.row(oxygen).iter().by_vals().collect(),
+        report.row(co2).iter().by_vals().collect(),
+    )
+}
+
+fn single_pass(report: &BitMatrix) -> (Vec<bool>, Vec<bool>) {
+    let (oxygen, co2) = life_support_ratings(report);
+
+    (
+        oxygen.bits().iter().by_vals().collect(),
+        co2.bits().iter().by_vals().collect(),
+    )
+}
+
+/// Median duration of the runs, with the result of the last one.
+fn time<T>(f: impl Fn() -> T) -> (Duration, T) {
+    let mut samples = vec![];
+    let mut result = None;
+
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        result = Some(black_box(f()));
+        samples.push(start.elapsed());
+    }
+    samples.sort_unstable();
+
+    (samples[RUNS / 2], result.expect("at least one run"))
+}
+
+fn main() {
+    let sizes = env::args()
+        .skip(1)
+        .filter(|arg| arg != "--bench")
+        .map(|arg| arg.parse().expect("report size has to be a number"))
+        .collect::<Vec<usize>>();
+    let sizes = if sizes.is_empty() {
+        SIZES.to_vec()
+    } else {
+        sizes
+    };
+
+    println!(
+        "{:>9} {:>6}  {:>12} {:>12} {:>12}",
+        "rows", "width", "in place", "two filters", "single pass"
+    );
+
+    for size in sizes {
+        let input = generator::generate::<Day3>(7, Some(size)).input;
+        let report: BitMatrix = input.parse().expect("generated report is valid");
+
+        let (in_place_time, expected) = time(|| in_place(&report));
+        let (two_filters_time, two_filters_ratings) = time(|| two_filters(&report));
+        let (single_pass_time, single_pass_ratings) = time(|| single_pass(&report));
+        assert_eq!(expected, two_filters_ratings, "two filters disagree");
+        assert_eq!(expected, single_pass_ratings, "single pass disagrees");
+
+        println!(
+            "{:>9} {:>6}  {:>10.2}ms {:>10.2}ms {:>10.2}ms",
+            report.height(),
+            report.width(),
+            in_place_time.as_secs_f64() * 1e3,
+            two_filters_time.as_secs_f64() * 1e3,
+            single_pass_time.as_secs_f64() * 1e3
+        );
+    }
+    println!("medians of {} runs", RUNS);
+}
@@ -0,0 +1,187 @@
+use crate::matrix::Bits;
+use std::fmt::Display;
+use std::ops::Mul;
+
+/// Unsigned integer of any size, stored as base 2^32 digits from the least significant one.
+/// Only supports what the ratings need: building from bits, multiplying and printing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BigUint(Vec<u32>);
+
+impl BigUint {
+    /// Number written by the bits, the most significant bit first.
+    pub fn from_bits(bits: &Bits) -> Self {
+        let mut digits = vec![0; bits.len().div_ceil(32)];
+        for idx in bits.iter_ones() {
+            let power = bits.len() - 1 - idx;
+            digits[power / 32] |= 1 << (power % 32);
+        }
+
+        let mut number = Self(digits);
+        number.normalize();
+        number
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Drops leading zero digits, so that every number has a single representation.
+    fn normalize(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+
+    /// Divides in place, returning the remainder.
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for digit in self.0.iter_mut().rev() {
+            let current = (remainder << 32) | u64::from(*digit);
+            *digit = (current / u64::from(divisor)) as u32;
+            remainder = current % u64::from(divisor);
+        }
+
+        self.normalize();
+        remainder as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        let mut number = Self(vec![value as u32, (value >> 32) as u32]);
+        number.normalize();
+        number
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut digits = vec![0u32; self.0.len() + rhs.0.len()];
+
+        for (i, &a) in self.0.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in rhs.0.iter().enumerate() {
+                let current = u64::from(a) * u64::from(b) + u64::from(digits[i + j]) + carry;
+                digits[i + j] = current as u32;
+                carry = current >> 32;
+            }
+            digits[i + rhs.0.len()] = carry as u32;
+        }
+
+        let mut product = BigUint(digits);
+        product.normalize();
+        product
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut rest = self.clone();
+        let mut chunks = vec![];
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(CHUNK));
+        }
+
+        let mut chunks = chunks.iter().rev();
+        if let Some(most_significant) = chunks.next() {
+            write!(f, "{}", most_significant)?;
+        }
+        for chunk in chunks {
+            write!(f, "{:09}", chunk)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::BitMatrix;
+
+    fn from_binary(bits: &str) -> BigUint {
+        let report: BitMatrix = bits.parse().expect("bits should parse");
+        BigUint::from_bits(report.row(0))
+    }
+
+    #[test]
+    fn reads_bits_most_significant_first() {
+        assert_eq!(from_binary("10111"), BigUint::from(23));
+        assert_eq!(from_binary("000"), BigUint::default());
+        assert_eq!(
+            from_binary(&format!("1{}", "0".repeat(64))),
+            BigUint(vec![0, 0, 1])
+        );
+    }
+
+    #[test]
+    fn carries_across_limbs() {
+        let max_limb = BigUint::from(u64::from(u32::MAX));
+        assert_eq!(
+            &max_limb * &max_limb,
+            BigUint::from(u64::from(u32::MAX) * u64::from(u32::MAX))
+        );
+
+        let max = BigUint::from(u64::MAX);
+        let expected = u128::from(u64::MAX) * u128::from(u64::MAX);
+        assert_eq!(
+            &max * &max,
+            BigUint(vec![
+                expected as u32,
+                (expected >> 32) as u32,
+                (expected >> 64) as u32,
+                (expected >> 96) as u32,
+            ])
+        );
+        assert_eq!((&max * &max).to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn multiplies_numbers_wider_than_128_bits() {
+        let a = from_binary(&"1".repeat(100));
+        let b = from_binary(&format!("1{}1", "0".repeat(69)));
+
+        assert_eq!(
+            (&a * &b).to_string(),
+            "1496577676626844588241840919300521449908454215909375"
+        );
+        assert_eq!(&a * &b, &b * &a);
+        assert!((&a * &BigUint::default()).is_zero());
+    }
+
+    #[test]
+    fn pads_inner_chunks_to_nine_digits() {
+        assert_eq!(BigUint::default().to_string(), "0");
+        assert_eq!(BigUint::from(999_999_999).to_string(), "999999999");
+        assert_eq!(BigUint::from(1_000_000_000).to_string(), "1000000000");
+        assert_eq!(
+            BigUint::from(1_000_000_007_000_000_000).to_string(),
+            "1000000007000000000"
+        );
+        assert_eq!(
+            (&BigUint::from(1_000_000_000) * &BigUint::from(u64::MAX)).to_string(),
+            "18446744073709551615000000000"
+        );
+    }
+
+    #[test]
+    fn multiplies_the_rates_of_a_wide_report() {
+        let row = format!("{}{}", "1".repeat(33), "0".repeat(33));
+        let report: BitMatrix = format!("{row}\n{row}\n{}\n", "0".repeat(66))
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            crate::power_consumption(&report).to_string(),
+            "633825299966540748167265124352"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::matrix::{BitMatrix, Bits};
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// Bits of the remaining candidates in the column being filtered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitCounts {
+    pub column: usize,
+    pub ones: usize,
+    pub zeros: usize,
+}
+
+/// Rule deciding which candidates survive a column of the report.
+pub trait BitCriterion {
+    /// Bit the candidates need to have in the column to survive, `None` keeps all of them.
+    fn keep(&self, counts: BitCounts) -> Option<bool>;
+}
+
+impl<F: Fn(BitCounts) -> Option<bool>> BitCriterion for F {
+    fn keep(&self, counts: BitCounts) -> Option<bool> {
+        self(counts)
+    }
+}
+
+/// Keeps the most common bit, `ties` if both are equally common. A column where all candidates
+/// agree keeps every one of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Majority {
+    pub ties: bool,
+}
+
+impl BitCriterion for Majority {
+    fn keep(&self, counts: BitCounts) -> Option<bool> {
+        if counts.ones == 0 || counts.zeros == 0 {
+            None
+        } else if counts.ones == counts.zeros {
+            Some(self.ties)
+        } else {
+            Some(counts.ones > counts.zeros)
+        }
+    }
+}
+
+/// Keeps the least common bit, `ties` if both are equally common. A bit none of the candidates
+/// has does not count, so a column where they all agree keeps every one of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Minority {
+    pub ties: bool,
+}
+
+impl BitCriterion for Minority {
+    fn keep(&self, counts: BitCounts) -> Option<bool> {
+        if counts.ones == 0 || counts.zeros == 0 {
+            None
+        } else if counts.ones == counts.zeros {
+            Some(self.ties)
+        } else {
+            Some(counts.ones < counts.zeros)
+        }
+    }
+}
+
+/// Keeps the candidates matching a fixed pattern like `10?1`. Columns marked `?` and those
+/// past the end of the pattern keep every candidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern(Vec<Option<bool>>);
+
+impl BitCriterion for Pattern {
+    fn keep(&self, counts: BitCounts) -> Option<bool> {
+        self.0.get(counts.column).copied().flatten()
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .chars()
+            .map(|bit| match bit {
+                '0' => Ok(Some(false)),
+                '1' => Ok(Some(true)),
+                '?' => Ok(None),
+                _ => Err(anyhow!("invalid pattern bit {:?}, expected 0, 1 or ?", bit)),
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
+/// A separate criterion for every column. Columns without one keep every candidate.
+#[derive(Default)]
+pub struct PerColumn(pub Vec<Box<dyn BitCriterion>>);
+
+impl BitCriterion for PerColumn {
+    fn keep(&self, counts: BitCounts) -> Option<bool> {
+        self.0
+            .get(counts.column)
+            .and_then(|criterion| criterion.keep(counts))
+    }
+}
+
+/// What a criterion did to a single column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterStep {
+    pub counts: BitCounts,
+    /// Bit the candidates needed to survive, `None` if the criterion kept all of them.
+    pub kept: Option<bool>,
+    /// Rows eliminated in this column, as indices into the report.
+    pub eliminated: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filtered {
+    /// Rows which survived every column, as indices into the report in their original order.
+    pub survivors: Vec<usize>,
+    /// One step per column, up to the one which left a single candidate.
+    pub trace: Vec<FilterStep>,
+}
+
+impl Filtered {
+    pub fn rows<'a>(&'a self, report: &'a BitMatrix) -> impl Iterator<Item = &'a Bits> + 'a {
+        self.survivors.iter().map(|&row| report.row(row))
+    }
+}
+
+/// Narrows down the rows of the report column by column, from the first one, until a single
+/// row is left or the columns run out. The report itself is left untouched.
+pub fn filter_rows(report: &BitMatrix, criterion: &(impl BitCriterion + ?Sized)) -> Filtered {
+    let mut survivors: Vec<usize> = (0..report.height()).collect();
+    let mut trace = vec![];
+
+    for column in 0..report.width() {
+        if survivors.len() <= 1 {
+            break;
+        }
+
+        let bits = report.column(column);
+        let ones = survivors.iter().filter(|&&row| bits[row]).count();
+        let counts = BitCounts {
+            column,
+            ones,
+            zeros: survivors.len() - ones,
+        };
+
+        let kept = criterion.keep(counts);
+        let mut eliminated = vec![];
+        if let Some(kept) = kept {
+            survivors.retain(|&row| {
+                let survives = bits[row] == kept;
+                if !survives {
+                    eliminated.push(row);
+                }
+                survives
+            });
+        }
+
+        trace.push(FilterStep {
+            counts,
+            kept,
+            eliminated,
+        });
+    }
+
+    Filtered { survivors, trace }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str =
+        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
+
+    fn report(s: &str) -> BitMatrix {
+        s.parse().expect("report should parse")
+    }
+
+    fn step(
+        column: usize,
+        (ones, zeros): (usize, usize),
+        kept: Option<bool>,
+        eliminated: &[usize],
+    ) -> FilterStep {
+        FilterStep {
+            counts: BitCounts {
+                column,
+                ones,
+                zeros,
+            },
+            kept,
+            eliminated: eliminated.to_vec(),
+        }
+    }
+
+    #[test]
+    fn majority_finds_the_example_oxygen_rating() {
+        let filtered = filter_rows(&report(EXAMPLE), &Majority { ties: true });
+
+        assert_eq!(filtered.survivors, [3]);
+        assert_eq!(
+            filtered.trace,
+            [
+                step(0, (7, 5), Some(true), &[0, 5, 6, 10, 11]),
+                step(1, (3, 4), Some(false), &[1, 7, 9]),
+                step(2, (3, 1), Some(true), &[8]),
+                step(3, (2, 1), Some(true), &[4]),
+                step(4, (1, 1), Some(true), &[2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn minority_finds_the_example_co2_rating() {
+        let filtered = filter_rows(&report(EXAMPLE), &Minority { ties: false });
+
+        assert_eq!(filtered.survivors, [11]);
+        assert_eq!(
+            filtered.trace,
+            [
+                step(0, (7, 5), Some(false), &[1, 2, 3, 4, 7, 8, 9]),
+                step(1, (2, 3), Some(true), &[0, 6, 10]),
+                step(2, (1, 1), Some(false), &[5]),
+            ]
+        );
+    }
+
+    #[test]
+    fn uniform_columns_keep_every_candidate() {
+        let report = report("101\n100\n111\n");
+
+        let filtered = filter_rows(&report, &Majority { ties: true });
+        assert_eq!(filtered.survivors, [0]);
+        assert_eq!(
+            filtered.trace,
+            [
+                step(0, (3, 0), None, &[]),
+                step(1, (1, 2), Some(false), &[2]),
+                step(2, (1, 1), Some(true), &[1]),
+            ]
+        );
+
+        let filtered = filter_rows(&report, &Minority { ties: false });
+        assert_eq!(filtered.survivors, [2]);
+        assert_eq!(
+            filtered.trace,
+            [
+                step(0, (3, 0), None, &[]),
+                step(1, (1, 2), Some(true), &[0, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_ignores_wildcards_and_columns_past_its_end() {
+        let pattern: Pattern = "1?1".parse().unwrap();
+        let filtered = filter_rows(&report(EXAMPLE), &pattern);
+
+        assert_eq!(filtered.survivors, [1, 2, 3, 4, 7]);
+        assert_eq!(
+            filtered
+                .trace
+                .iter()
+                .map(|step| step.kept)
+                .collect::<Vec<_>>(),
+            [Some(true), None, Some(true), None, None]
+        );
+        assert!(filtered.trace[1].eliminated.is_empty());
+        assert!("10x".parse::<Pattern>().is_err());
+    }
+
+    #[test]
+    fn per_column_criteria_apply_to_their_own_column_only() {
+        let criteria = PerColumn(vec![
+            Box::new(Minority { ties: false }),
+            Box::new(|_: BitCounts| Some(true)),
+        ]);
+        let filtered = filter_rows(&report(EXAMPLE), &criteria);
+
+        assert_eq!(filtered.survivors, [5, 11]);
+        assert_eq!(
+            filtered
+                .trace
+                .iter()
+                .map(|step| step.kept)
+                .collect::<Vec<_>>(),
+            [Some(false), Some(true), None, None, None]
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use crate::Day3;
+use rand::rngs::StdRng;
+use rand::seq::index;
+use utils::generator::{GeneratedInput, Generator};
+
+const REPORT_WIDTH: usize = 12;
+
+impl Generator for Day3 {
+    const DEFAULT_SIZE: usize = 1000;
+
+    /// `size` distinct 12-bit numbers - duplicates could leave more than one rating candidate.
+    /// Reports of more than 4096 numbers are as wide as it takes to keep them distinct.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let size = size.max(1);
+        let width = REPORT_WIDTH.max(size.next_power_of_two().trailing_zeros() as usize);
+
+        index::sample(rng, 1 << width, size)
+            .into_iter()
+            .map(|number| format!("{:0width$b}\n", number, width = width))
+            .collect::<String>()
+            .into()
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::{anyhow, Result};
+use bitvec::order::Msb0;
+use std::fmt::Display;
+
+use bitvec::prelude::BitVec;
+use utils::Solution;
+
+mod biguint;
+mod criteria;
+mod generator;
+mod matrix;
+pub use biguint::BigUint;
+pub use criteria::{
+    filter_rows, BitCounts, BitCriterion, FilterStep, Filtered, Majority, Minority, Pattern,
+    PerColumn,
+};
+pub use matrix::{BitMatrix, Bits};
+
+/// Rating read from a diagnostic report, as wide as the report. Displays as a bit string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating(BitVec<usize, Msb0>);
+
+impl Rating {
+    pub fn bits(&self) -> &Bits {
+        &self.0
+    }
+
+    pub fn value(&self) -> BigUint {
+        BigUint::from_bits(&self.0)
+    }
+}
+
+impl Display for Rating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for bit in self.0.iter() {
+            write!(f, "{}", u8::from(*bit))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Gamma and epsilon rates, built from the most and the least common bit of every column.
+pub fn power_rates(report: &BitMatrix) -> (Rating, Rating) {
+    let mut gamma_rate: BitVec<usize, Msb0> = BitVec::with_capacity(report.width());
+    let mut epsilon_rate: BitVec<usize, Msb0> = BitVec::with_capacity(report.width());
+
+    for column in 0..report.width() {
+        let ones_count = report.count_ones(column, 0..report.height());
+        let zeros_count = report.height() - ones_count;
+
+        gamma_rate.push(ones_count > zeros_count);
+        epsilon_rate.push(zeros_count > ones_count);
+    }
+
+    (Rating(gamma_rate), Rating(epsilon_rate))
+}
+
+/// Gamma rate multiplied by epsilon rate.
+pub fn power_consumption(report: &BitMatrix) -> BigUint {
+    let (gamma_rate, epsilon_rate) = power_rates(report);
+
+    &gamma_rate.value() * &epsilon_rate.value()
+}
+
+/// Moves the rows satisfying `predicate` to the front of the slice and returns how many there are.
+fn partition(rows: &mut [usize], predicate: impl Fn(usize) -> bool) -> usize {
+    let mut next_idx = 0;
+    for i in 0..rows.len() {
+        if predicate(rows[i]) {
+            rows.swap(next_idx, i);
+            next_idx += 1;
+        }
+    }
+
+    next_idx
+}
+
+/// Oxygen generator and CO2 scrubber ratings. Panics if the report is empty.
+///
+/// Both ratings are searched for in a single pass over the columns. Candidates of both searches
+/// share one buffer of row indices, partitioned three ways into `[oxygen | eliminated | co2]`.
+/// Until the first column where the candidates disagree both searches keep every row, there
+/// the majority goes to the front and the minority to the back. From then on every column
+/// moves the rows each search eliminates towards the middle. The report itself is not modified.
+pub fn life_support_ratings(report: &BitMatrix) -> (Rating, Rating) {
+    let oxygen_criterion = Majority { ties: true };
+    let co2_criterion = Minority { ties: false };
+
+    let height = report.height();
+    let mut rows: Vec<usize> = (0..height).collect();
+    let mut split = false;
+    let mut oxygen_end = height;
+    let mut co2_start = 0;
+
+    for column in 0..report.width() {
+        if oxygen_end <= 1 && height - co2_start <= 1 {
+            break;
+        }
+
+        let bits = report.column(column);
+        let counts = |rows: &[usize]| {
+            let ones = rows.iter().filter(|&&row| bits[row]).count();
+            BitCounts {
+                column,
+                ones,
+                zeros: rows.len() - ones,
+            }
+        };
+
+        if !split {
+            let counts = counts(&rows);
+            let oxygen_bit = oxygen_criterion.keep(counts);
+            if oxygen_bit != co2_criterion.keep(counts) {
+                oxygen_end = partition(&mut rows, |row| Some(bits[row]) == oxygen_bit);
+                co2_start = oxygen_end;
+                split = true;
+            }
+            continue;
+        }
+
+        if oxygen_end > 1 {
+            let oxygen_bit = oxygen_criterion.keep(counts(&rows[..oxygen_end]));
+            oxygen_end = partition(&mut rows[..oxygen_end], |row| {
+                oxygen_bit.is_none_or(|bit| bits[row] == bit)
+            });
+        }
+
+        if height - co2_start > 1 {
+            let co2_bit = co2_criterion.keep(counts(&rows[co2_start..]));
+            co2_start += partition(&mut rows[co2_start..], |row| {
+                co2_bit.is_some_and(|bit| bits[row] != bit)
+            });
+        }
+    }
+
+    (
+        Rating(report.row(rows[0]).to_bitvec()),
+        Rating(report.row(rows[height - 1]).to_bitvec()),
+    )
+}
+
+/// Oxygen generator rating multiplied by CO2 scrubber rating. Panics if the report is empty.
+pub fn life_support_rating(report: &BitMatrix) -> BigUint {
+    let (oxygen_generator_rating, co2_scrubber_rating) = life_support_ratings(report);
+
+    &oxygen_generator_rating.value() * &co2_scrubber_rating.value()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = BitMatrix;
+    type Part1 = BigUint;
+    type Part2 = BigUint;
+    // The CO2 scrubber rating used to drop every candidate when the remaining ones agreed on a bit,
+    // and reports wider than 64 bits used to be truncated.
+    const VERSION: u32 = 3;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        if input.lines().next().is_none() {
+            return Err(anyhow!("diagnostic report is empty"));
+        }
+
+        input.parse()
+    }
+
+    fn part1(report: &Self::Input) -> Result<Self::Part1> {
+        Ok(power_consumption(report))
+    }
+
+    fn part2(report: &Self::Input) -> Result<Self::Part2> {
+        Ok(life_support_rating(report))
+    }
+}
@@ -0,0 +1,195 @@
+use anyhow::Result;
+use bitvec::order::Msb0;
+use bitvec::slice::BitSlice;
+use bitvec::vec::BitVec;
+use std::ops::Range;
+use std::str::FromStr;
+use utils::parser::{many1, opt, parse_lines, satisfy, space, Input, Parser};
+
+pub type Bits = BitSlice<usize, Msb0>;
+
+/// Matrix of bits stored both row by row and column by column, so rows and columns are both
+/// contiguous slices. Every row operation keeps the two layouts in sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    width: usize,
+    height: usize,
+    rows: BitVec<usize, Msb0>,
+    columns: BitVec<usize, Msb0>,
+}
+
+impl BitMatrix {
+    /// Panics if the rows are not all of the same width.
+    pub fn from_rows<R: IntoIterator<Item = bool>>(rows: impl IntoIterator<Item = R>) -> Self {
+        let mut width = None;
+        let mut height = 0;
+        let mut row_major: BitVec<usize, Msb0> = BitVec::new();
+
+        for row in rows {
+            let start = row_major.len();
+            row_major.extend(row);
+
+            let row_width = row_major.len() - start;
+            assert_eq!(
+                *width.get_or_insert(row_width),
+                row_width,
+                "rows have to be of equal width"
+            );
+            height += 1;
+        }
+
+        let width = width.unwrap_or_default();
+        let mut columns = BitVec::with_capacity(width * height);
+        for column in 0..width {
+            columns.extend((0..height).map(|row| row_major[row * width + column]));
+        }
+
+        Self {
+            width,
+            height,
+            rows: row_major,
+            columns,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn row(&self, row: usize) -> &Bits {
+        &self.rows[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, column: usize) -> &Bits {
+        &self.columns[column * self.height..(column + 1) * self.height]
+    }
+
+    /// Number of set bits of the column within the given rows.
+    pub fn count_ones(&self, column: usize, rows: Range<usize>) -> usize {
+        self.column(column)[rows].count_ones()
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        for column in 0..self.width {
+            self.rows
+                .swap(a * self.width + column, b * self.width + column);
+            self.columns
+                .swap(column * self.height + a, column * self.height + b);
+        }
+    }
+
+    /// Moves the rows within `rows` which satisfy `predicate` to the beginning of the range,
+    /// keeping their relative order, and returns how many there are.
+    pub fn partition_rows(
+        &mut self,
+        rows: Range<usize>,
+        mut predicate: impl FnMut(&Bits) -> bool,
+    ) -> usize {
+        let start = rows.start;
+        let mut next_row = start;
+
+        for row in rows {
+            if predicate(self.row(row)) {
+                self.swap_rows(next_row, row);
+                next_row += 1;
+            }
+        }
+
+        next_row - start
+    }
+}
+
+/// Row of a report. With `width` set the row has to have exactly that many bits.
+fn row<'a>(width: Option<usize>) -> impl Parser<'a, Vec<bool>> {
+    let bit = satisfy("0 or 1", |bit| match bit {
+        '0' => Some(false),
+        '1' => Some(true),
+        _ => None,
+    });
+    let bits = many1(bit);
+
+    move |input: Input<'a>| {
+        let (bits, rest) = bits.parse(input)?;
+        if !rest.rest().starts_with(char::is_whitespace) && !rest.is_empty() {
+            return Err(rest.error("0 or 1"));
+        }
+
+        match width {
+            Some(width) if bits.len() != width => {
+                let at = if bits.len() < width {
+                    rest
+                } else {
+                    input.advance(width)
+                };
+                Err(at.error(format!("{} bits like the first line", width)))
+            }
+            _ => opt(space()).parse(rest).map(|(_, rest)| (bits, rest)),
+        }
+    }
+}
+
+/// Report of `0` and `1` characters, one row per line. Every line has to be as wide as the first.
+impl FromStr for BitMatrix {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, _) = row(None).parse(Input::new(s))?;
+        let rows = parse_lines(s, row(Some(first.len())))?;
+
+        Ok(Self::from_rows(rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::parser::ParseError;
+
+    fn parse_error(report: &str) -> ParseError {
+        report
+            .parse::<BitMatrix>()
+            .expect_err("report should be rejected")
+            .downcast()
+            .expect("should be a parse error")
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let shorter = parse_error("0101\n011\n");
+        assert_eq!((shorter.line(), shorter.column()), (2, 4));
+        assert_eq!(shorter.expected(), ["4 bits like the first line"]);
+
+        let longer = parse_error("0101\n0110\n01101\n");
+        assert_eq!((longer.line(), longer.column()), (3, 5));
+        assert_eq!(longer.expected(), ["4 bits like the first line"]);
+    }
+
+    #[test]
+    fn rejects_non_binary_characters() {
+        let error = parse_error("0101\n0121\n");
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert_eq!(error.expected(), ["0 or 1"]);
+
+        let error = parse_error("\n0101\n");
+        assert_eq!((error.line(), error.column()), (1, 1));
+        assert_eq!(error.expected(), ["0 or 1"]);
+    }
+
+    #[test]
+    fn parses_reports_wider_than_64_bits() {
+        let first = "1".repeat(70);
+        let second = format!("{}1", "0".repeat(69));
+        let report: BitMatrix = format!("{}\n{}\n", first, second).parse().unwrap();
+
+        assert_eq!((report.width(), report.height()), (70, 2));
+        assert!(report.row(0).all());
+        assert_eq!(report.row(1).count_ones(), 1);
+        assert!(report.row(1)[69]);
+        assert_eq!(report.count_ones(69, 0..2), 2);
+        assert_eq!(report.count_ones(64, 0..2), 1);
+    }
+}
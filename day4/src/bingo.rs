@@ -1,6 +1,6 @@
 use std::{collections::HashMap, io, str::Lines};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BingoBoard {
     board: HashMap<usize, (usize, usize)>,
     col_sums: [(usize, usize); 5],
@@ -0,0 +1,41 @@
+use crate::Day4;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use utils::generator::{GeneratedInput, Generator};
+
+const NUMBERS: usize = 100;
+const BOARD_SIZE: usize = 5;
+
+impl Generator for Day4 {
+    const DEFAULT_SIZE: usize = 100;
+
+    /// All of 0..100 drawn in random order and `size` boards. Since every number is drawn,
+    /// every board wins eventually.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let mut numbers = (0..NUMBERS).collect::<Vec<_>>();
+        numbers.shuffle(rng);
+
+        let guesses = numbers
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let boards = (0..size.max(1))
+            .map(|_| {
+                let board = numbers
+                    .choose_multiple(rng, BOARD_SIZE * BOARD_SIZE)
+                    .map(|number| format!("{:>2}", number))
+                    .collect::<Vec<_>>();
+
+                board
+                    .chunks(BOARD_SIZE)
+                    .map(|row| format!("{}\n", row.join(" ")))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("{}\n\n{}", guesses, boards).into()
+    }
+}
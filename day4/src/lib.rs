@@ -0,0 +1,78 @@
+use anyhow::{anyhow, Result};
+
+mod bingo;
+mod generator;
+
+pub use bingo::BingoBoard;
+use utils::Solution;
+
+fn read_guesses<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Option<Vec<usize>> {
+    lines
+        .next()
+        .map(|line| line.split(',').flat_map(str::parse).collect())
+}
+
+fn read_input(data: &str) -> Result<(Vec<usize>, Vec<BingoBoard>)> {
+    let mut data_lines = data.lines();
+
+    let guesses =
+        read_guesses(&mut data_lines).ok_or_else(|| anyhow!("failed to find guesses line"))?;
+
+    let mut boards = vec![];
+    while let Ok(board) = BingoBoard::try_from(&mut data_lines) {
+        boards.push(board);
+    }
+
+    Ok((guesses, boards))
+}
+
+/// Plays the guesses on all boards, returning scores of the first and the last board to win.
+pub fn simulate_bingo_game(guesses: &[usize], boards: &mut [BingoBoard]) -> (usize, usize) {
+    let mut boards_won = 0;
+    let total_boards = boards.len();
+    let mut result = (0, 0);
+
+    for guess in guesses.iter().copied() {
+        boards.iter_mut().for_each(|board| {
+            let mark_score = board.mark(guess);
+            mark_score.iter().copied().for_each(|score| {
+                boards_won += 1;
+                if boards_won == 1 {
+                    result.0 = score;
+                } else if boards_won == total_boards {
+                    result.1 = score;
+                }
+            });
+        });
+
+        if boards_won == total_boards {
+            break;
+        }
+    }
+
+    result
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = (Vec<usize>, Vec<BingoBoard>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input)
+    }
+
+    fn part1((guesses, boards): &Self::Input) -> Result<Self::Part1> {
+        let (first_won_score, _) = simulate_bingo_game(guesses, &mut boards.clone());
+
+        Ok(first_won_score)
+    }
+
+    fn part2((guesses, boards): &Self::Input) -> Result<Self::Part2> {
+        let (_, last_won_score) = simulate_bingo_game(guesses, &mut boards.clone());
+
+        Ok(last_won_score)
+    }
+}
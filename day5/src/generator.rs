@@ -0,0 +1,42 @@
+use crate::Day5;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+const FLOOR_SIZE: isize = 1000;
+
+impl Generator for Day5 {
+    const DEFAULT_SIZE: usize = 500;
+
+    /// `size` vents - horizontal, vertical or diagonal at exactly 45 degrees.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        (0..size)
+            .map(|_| {
+                let (x1, y1) = (rng.gen_range(0..FLOOR_SIZE), rng.gen_range(0..FLOOR_SIZE));
+                let length = rng.gen_range(1..FLOOR_SIZE / 2);
+                let (dx, dy) = match rng.gen_range(0..3) {
+                    0 => (1, 0),
+                    1 => (0, 1),
+                    _ => (1, if rng.gen() { 1 } else { -1 }),
+                };
+                let (dx, dy) = if rng.gen() { (dx, dy) } else { (-dx, -dy) };
+
+                // Shorten the vent until it fits on the floor.
+                let fits = |length| {
+                    (0..FLOOR_SIZE).contains(&(x1 + dx * length))
+                        && (0..FLOOR_SIZE).contains(&(y1 + dy * length))
+                };
+                let length = (0..=length).rev().find(|length| fits(*length)).unwrap_or(0);
+
+                format!(
+                    "{},{} -> {},{}\n",
+                    x1,
+                    y1,
+                    x1 + dx * length,
+                    y1 + dy * length
+                )
+            })
+            .collect::<String>()
+            .into()
+    }
+}
@@ -0,0 +1,17 @@
+use crate::Day6;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day6 {
+    const DEFAULT_SIZE: usize = 300;
+
+    /// `size` lanternfish with timers between 1 and 5, like in the puzzle.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let timers = (0..size.max(1))
+            .map(|_| rng.gen_range(1..=5).to_string())
+            .collect::<Vec<_>>();
+
+        format!("{}\n", timers.join(",")).into()
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use utils::Solution;
+
+mod generator;
+
+/// Number of lanternfish after `days` days.
+pub fn simulate_growth(lanternfishes: &[usize], days: usize) -> usize {
+    let mut histogram: [usize; 9] = [0; 9];
+    lanternfishes.iter().copied().for_each(|fish_age| {
+        histogram[fish_age] += 1;
+    });
+
+    let mut day_no = 1;
+    while day_no <= days {
+        let mut new_histogram = [0; 9];
+
+        let new_lanternfish_count = histogram[0];
+
+        new_histogram[..8].copy_from_slice(&histogram[1..]);
+
+        new_histogram[8] += new_lanternfish_count;
+        new_histogram[6] += new_lanternfish_count;
+        histogram = new_histogram;
+        day_no += 1;
+    }
+
+    histogram.into_iter().sum()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input
+            .lines()
+            .flat_map(|line| line.split(','))
+            .flat_map(str::parse)
+            .collect())
+    }
+
+    fn part1(lanternfishes: &Self::Input) -> Result<Self::Part1> {
+        Ok(simulate_growth(lanternfishes, 80))
+    }
+
+    fn part2(lanternfishes: &Self::Input) -> Result<Self::Part2> {
+        Ok(simulate_growth(lanternfishes, 256))
+    }
+}
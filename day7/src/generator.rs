@@ -0,0 +1,20 @@
+use crate::Day7;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day7 {
+    const DEFAULT_SIZE: usize = 1000;
+
+    /// `size` crab positions, crowded towards the lower end like in the puzzle.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let positions = (0..size.max(1))
+            .map(|_| {
+                let spread: f64 = rng.gen();
+                ((spread * spread * 2000.0) as usize).to_string()
+            })
+            .collect::<Vec<_>>();
+
+        format!("{}\n", positions.join(",")).into()
+    }
+}
@@ -0,0 +1,50 @@
+use crate::Day8;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+fn scrambled(rng: &mut StdRng, wiring: &[char], digit: usize) -> String {
+    let mut segments = DIGIT_SEGMENTS[digit]
+        .chars()
+        .map(|segment| wiring[(segment as u8 - b'a') as usize])
+        .collect::<Vec<_>>();
+    segments.shuffle(rng);
+
+    segments.into_iter().collect()
+}
+
+impl Generator for Day8 {
+    const DEFAULT_SIZE: usize = 200;
+
+    /// `size` displays, each with its own random wiring of segments.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        (0..size)
+            .map(|_| {
+                let mut wiring = ('a'..='g').collect::<Vec<_>>();
+                wiring.shuffle(rng);
+
+                let mut digits = (0..10).collect::<Vec<_>>();
+                digits.shuffle(rng);
+
+                let patterns = digits
+                    .into_iter()
+                    .map(|digit| scrambled(rng, &wiring, digit))
+                    .collect::<Vec<_>>();
+                let output = (0..4)
+                    .map(|_| {
+                        let digit = rng.gen_range(0..10);
+                        scrambled(rng, &wiring, digit)
+                    })
+                    .collect::<Vec<_>>();
+
+                format!("{} | {}\n", patterns.join(" "), output.join(" "))
+            })
+            .collect::<String>()
+            .into()
+    }
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+pub use notepad::NoteEntry;
+use utils::Solution;
+
+mod generator;
+mod notepad;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<NoteEntry>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().flat_map(str::parse).collect())
+    }
+
+    fn part1(note_entries: &Self::Input) -> Result<Self::Part1> {
+        Ok(note_entries
+            .iter()
+            .map(NoteEntry::unique_segments_digits_count)
+            .sum())
+    }
+
+    fn part2(note_entries: &Self::Input) -> Result<Self::Part2> {
+        Ok(note_entries
+            .iter()
+            .map(NoteEntry::unscrambled_output_value)
+            .sum())
+    }
+}
@@ -1,29 +1,21 @@
 use anyhow::Result;
-use notepad::NoteEntry;
-use std::fs;
-
-mod notepad;
+use day8::Day8;
+use std::env;
+use utils::input;
+use utils::Solution;
 
 fn main() -> Result<()> {
-    let note_entries: Vec<NoteEntry> = fs::read_to_string("./input")?
-        .lines()
-        .flat_map(str::parse)
-        .collect();
+    let path = env::args().nth(1).unwrap_or_else(|| "./input".into());
+    let note_entries = Day8::parse_reader(input::open(&path)?)?;
 
     println!(
         "Number of appearances of 1, 4, 7, 8 in output values: {}",
-        note_entries
-            .iter()
-            .map(NoteEntry::unique_segments_digits_count)
-            .sum::<usize>()
+        Day8::part1(&note_entries)?
     );
 
     println!(
         "Sum of all output values in note entries: {}",
-        note_entries
-            .iter()
-            .map(NoteEntry::unscrambled_output_value)
-            .sum::<usize>()
+        Day8::part2(&note_entries)?
     );
 
     Ok(())
@@ -11,7 +11,7 @@ pub struct NoteEntry {
 }
 
 fn ascii_ord(ch: char) -> usize {
-    (ch as u8 - 'a' as u8) as usize
+    (ch as u8 - b'a') as usize
 }
 
 impl NoteEntry {
@@ -20,7 +20,7 @@ impl NoteEntry {
         self.output_value
             .iter()
             .map(String::len)
-            .filter(|segment_count| unique_segments_count.contains(&segment_count))
+            .filter(|segment_count| unique_segments_count.contains(segment_count))
             .count()
     }
 
@@ -151,10 +151,10 @@ impl NoteEntry {
 
     pub fn unscrambled_output_value(&self) -> usize {
         let proper_wiring = self.unscramble();
-        let proper_digits = self.output_value.iter().cloned().map(|digit| {
+        let proper_digits = self.output_value.iter().map(|digit| {
             let mut unscrambled_segments = digit
                 .chars()
-                .map(|segment| proper_wiring[(segment as u8 - 'a' as u8) as usize])
+                .map(|segment| proper_wiring[(segment as u8 - b'a') as usize])
                 .collect::<Vec<_>>();
 
             unscrambled_segments.sort_unstable();
@@ -165,7 +165,7 @@ impl NoteEntry {
             .into_iter()
             .enumerate()
             .map(|(pos, segment)| {
-                self.unscrambled_segments_to_digit(&segment) * (10 as usize).pow((3 - pos) as u32)
+                self.unscrambled_segments_to_digit(&segment) * 10_usize.pow((3 - pos) as u32)
             })
             .sum()
     }
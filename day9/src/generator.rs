@@ -0,0 +1,37 @@
+use crate::Day9;
+use rand::rngs::StdRng;
+use rand::Rng;
+use utils::generator::{GeneratedInput, Generator};
+
+impl Generator for Day9 {
+    const DEFAULT_SIZE: usize = 100;
+
+    /// `size` x `size` heightmap. Height grows with the distance to the nearest of random
+    /// low points and tops out at 9, so basins come out separated by ridges of nines.
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput {
+        let size = size.max(1);
+        let low_points = (0..(size * size / 50).max(1))
+            .map(|_| (rng.gen_range(0..size), rng.gen_range(0..size)))
+            .collect::<Vec<_>>();
+
+        (0..size)
+            .map(|y| {
+                let mut row = (0..size)
+                    .map(|x| {
+                        let distance = low_points
+                            .iter()
+                            .map(|&(lx, ly)| lx.abs_diff(x) + ly.abs_diff(y))
+                            .min()
+                            .unwrap_or_default();
+                        let height = (distance + rng.gen_range(0..2)).min(9);
+
+                        char::from_digit(height as u32, 10).unwrap_or('9')
+                    })
+                    .collect::<String>();
+                row.push('\n');
+                row
+            })
+            .collect::<String>()
+            .into()
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use std::str::FromStr;
+use utils::grid::Grid;
+use utils::Solution;
+
+mod generator;
+
+pub struct Heightmap {
+    data: Grid<usize>,
+}
+
+impl Heightmap {
+    /// Sum of risk levels of all low points.
+    pub fn risk_level(&self) -> usize {
+        self.low_points()
+            .into_iter()
+            .map(|(x, y)| self.data[(x, y)] + 1)
+            .sum()
+    }
+
+    pub fn low_points(&self) -> Vec<(usize, usize)> {
+        self.data
+            .positions()
+            .filter(|&(x, y)| {
+                self.data
+                    .neighbours4(x, y)
+                    .all(|neighbour| self.data[neighbour] > self.data[(x, y)])
+            })
+            .collect()
+    }
+
+    /// Basins as `(basin index, size)` pairs, one per low point.
+    pub fn basins(&self) -> Vec<(usize, usize)> {
+        let mut basin_map = Grid::new(self.data.width(), self.data.height(), 0);
+        let mut basins = vec![];
+
+        for (basin_idx, (x, y)) in (1..).zip(self.low_points()) {
+            let mut stack = vec![(x, y)];
+            basins.push((basin_idx, 0));
+            let (_, basin_size) = basins.last_mut().unwrap();
+            basin_map[(x, y)] = basin_idx;
+
+            while let Some((x, y)) = stack.pop() {
+                let value = self.data[(x, y)];
+                *basin_size += 1;
+
+                for neighbour in self.data.neighbours4(x, y) {
+                    let not_basin_already = basin_map[neighbour] == 0;
+                    let forms_basin = self.data[neighbour] != 9 && self.data[neighbour] > value;
+
+                    if not_basin_already && forms_basin {
+                        basin_map[neighbour] = basin_idx;
+                        stack.push(neighbour);
+                    }
+                }
+            }
+        }
+
+        basins
+    }
+}
+
+impl FromStr for Heightmap {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = Grid::parse(s, "digit", |digit| digit.to_digit(10).map(|d| d as usize))?;
+
+        Ok(Self { data })
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Heightmap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(heightmap: &Self::Input) -> Result<Self::Part1> {
+        Ok(heightmap.risk_level())
+    }
+
+    fn part2(heightmap: &Self::Input) -> Result<Self::Part2> {
+        let mut basins = heightmap.basins();
+        basins.sort_unstable_by_key(|basin| basin.1);
+
+        Ok(basins.iter().rev().take(3).map(|basin| basin.1).product())
+    }
+}
@@ -0,0 +1,304 @@
+use crate::gif::GifEncoder;
+use crate::grid::Grid;
+use crate::Solution;
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Solution whose puzzle is a grid evolving step by step, which makes for a nice animation.
+pub trait Animated: Solution {
+    /// Colours of the shades used in recorded frames, indexed by shade.
+    fn palette() -> Palette;
+
+    /// Captures the input and the state after every step. Without `steps` it runs for as many
+    /// steps as the puzzle needs.
+    fn record(input: &Self::Input, steps: Option<usize>) -> Result<Recording>;
+}
+
+/// Colours available to frames. Frames store indices into the palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette(Vec<[u8; 3]>);
+
+impl Palette {
+    pub fn new(colours: Vec<[u8; 3]>) -> Self {
+        Self(colours)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Perceived brightness of the colour, used for greyscale output.
+    fn luma(&self, shade: u8) -> u8 {
+        let [r, g, b] = self.0[shade as usize].map(u32::from);
+        ((299 * r + 587 * g + 114 * b) / 1000) as u8
+    }
+}
+
+/// Comma separated hex colours, e.g. `000000,ff8800,#ffffff`.
+impl FromStr for Palette {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colours = s
+            .split(',')
+            .map(|colour| {
+                let hex = colour.trim().trim_start_matches('#');
+                let rgb = u32::from_str_radix(hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() == 6)
+                    .ok_or_else(|| anyhow!("invalid colour {}, expected rrggbb", colour))?;
+                let [_, r, g, b] = rgb.to_be_bytes();
+
+                Ok([r, g, b])
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if colours.len() > 256 {
+            return Err(anyhow!(
+                "palette has {} colours, at most 256 are supported",
+                colours.len()
+            ));
+        }
+
+        Ok(Self(colours))
+    }
+}
+
+/// Snapshot of a grid with one palette index (shade) per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    pub fn from_grid<T>(grid: &Grid<T>, shade: impl Fn(&T) -> u8) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.iter().map(shade).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Every cell becomes a `scale` x `scale` square of pixels.
+    fn scaled(&self, scale: usize) -> Self {
+        let pixels = (0..self.height * scale)
+            .flat_map(|y| (0..self.width * scale).map(move |x| (x / scale, y / scale)))
+            .map(|(x, y)| self.pixels[y * self.width + x])
+            .collect();
+
+        Self {
+            width: self.width * scale,
+            height: self.height * scale,
+            pixels,
+        }
+    }
+}
+
+/// Binary PBM image of the frame. Every row starts on a new byte, padded with white pixels.
+fn pbm(frame: &Frame, palette: &Palette) -> Vec<u8> {
+    let mut bytes = format!("P4\n{} {}\n", frame.width, frame.height).into_bytes();
+
+    for row in frame.pixels.chunks(frame.width.max(1)) {
+        for byte in row.chunks(8) {
+            let packed = byte.iter().enumerate().fold(0u8, |packed, (bit, shade)| {
+                packed | u8::from(palette.luma(*shade) < 128) << (7 - bit)
+            });
+            bytes.push(packed);
+        }
+    }
+
+    bytes
+}
+
+/// Sequence of captured frames, which can be written as numbered PBM/PGM images or an animated GIF.
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    frames: Vec<Frame>,
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn capture<T>(&mut self, grid: &Grid<T>, shade: impl Fn(&T) -> u8) {
+        self.frames.push(Frame::from_grid(grid, shade));
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    fn scaled_frames<'a>(
+        &'a self,
+        scale: usize,
+        palette: &'a Palette,
+    ) -> Result<impl Iterator<Item = Frame> + 'a> {
+        if scale == 0 {
+            return Err(anyhow!("scale has to be at least 1"));
+        }
+
+        if let Some(shade) = self
+            .frames
+            .iter()
+            .flat_map(|frame| frame.pixels.iter())
+            .max()
+        {
+            if *shade as usize >= palette.len() {
+                return Err(anyhow!(
+                    "frames use {} shades, but the palette has only {} colours",
+                    *shade as usize + 1,
+                    palette.len()
+                ));
+            }
+        }
+
+        Ok(self.frames.iter().map(move |frame| frame.scaled(scale)))
+    }
+
+    fn write_sequence(
+        &self,
+        directory: &Path,
+        extension: &str,
+        scale: usize,
+        palette: &Palette,
+        encode: impl Fn(&Frame, &Palette) -> Vec<u8>,
+    ) -> Result<()> {
+        fs::create_dir_all(directory)
+            .with_context(|| format!("failed to create directory {}", directory.display()))?;
+
+        let digits = self.frames.len().to_string().len().max(4);
+        for (idx, frame) in self.scaled_frames(scale, palette)?.enumerate() {
+            let path = directory.join(format!(
+                "frame_{:0digits$}.{}",
+                idx,
+                extension,
+                digits = digits
+            ));
+
+            fs::write(&path, encode(&frame, palette))
+                .with_context(|| format!("failed to write frame {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every frame as a black and white image `frame_NNNN.pbm`. Colours darker than mid-grey become black.
+    pub fn write_pbm(&self, directory: &Path, scale: usize, palette: &Palette) -> Result<()> {
+        self.write_sequence(directory, "pbm", scale, palette, pbm)
+    }
+
+    /// Writes every frame as a greyscale image `frame_NNNN.pgm`.
+    pub fn write_pgm(&self, directory: &Path, scale: usize, palette: &Palette) -> Result<()> {
+        self.write_sequence(directory, "pgm", scale, palette, |frame, palette| {
+            let mut bytes = format!("P5\n{} {}\n255\n", frame.width, frame.height).into_bytes();
+            bytes.extend(frame.pixels.iter().map(|shade| palette.luma(*shade)));
+
+            bytes
+        })
+    }
+
+    /// Writes all frames as a looping animated GIF, showing every frame for `delay` hundredths of a second.
+    pub fn write_gif(
+        &self,
+        path: &Path,
+        scale: usize,
+        palette: &Palette,
+        delay: u16,
+    ) -> Result<()> {
+        if palette.is_empty() {
+            return Err(anyhow!("palette has no colours"));
+        }
+
+        let frames = self.scaled_frames(scale, palette)?.collect::<Vec<_>>();
+        let dimension = |size: usize| {
+            u16::try_from(size)
+                .map_err(|_| anyhow!("frame of {} pixels is too large for a GIF", size))
+        };
+
+        let width = dimension(frames.iter().map(Frame::width).max().unwrap_or(1))?;
+        let height = dimension(frames.iter().map(Frame::height).max().unwrap_or(1))?;
+
+        let mut encoder = GifEncoder::new(width, height, &palette.0);
+        for frame in frames.iter() {
+            encoder.frame(
+                dimension(frame.width)?,
+                dimension(frame.height)?,
+                &frame.pixels,
+                delay,
+            );
+        }
+
+        fs::write(path, encoder.finish())
+            .with_context(|| format!("failed to write animation {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn black_and_white() -> Palette {
+        Palette::new(vec![[255, 255, 255], [0, 0, 0]])
+    }
+
+    /// Frame of `width` x `height` cells, black where `black` returns true.
+    fn frame(width: usize, height: usize, black: impl Fn(usize, usize) -> bool) -> Frame {
+        let grid = Grid::from_fn(width, height, black);
+        Frame::from_grid(&grid, |black| u8::from(*black))
+    }
+
+    #[test]
+    fn pads_pbm_rows_to_whole_bytes() {
+        let bytes = pbm(&frame(3, 2, |x, y| x == y), &black_and_white());
+        assert_eq!(bytes, b"P4\n3 2\n\x80\x40");
+
+        let bytes = pbm(&frame(10, 2, |x, _| x % 3 == 0), &black_and_white());
+        let mut expected = b"P4\n10 2\n".to_vec();
+        expected.extend([0b1001_0010, 0b0100_0000, 0b1001_0010, 0b0100_0000]);
+        assert_eq!(bytes, expected);
+
+        let bytes = pbm(&frame(8, 1, |_, _| true), &black_and_white());
+        assert_eq!(bytes, b"P4\n8 1\n\xFF");
+    }
+
+    #[test]
+    fn pads_scaled_pbm_rows() {
+        let scaled = frame(3, 1, |x, _| x == 1).scaled(3);
+
+        assert_eq!((scaled.width(), scaled.height()), (9, 3));
+        let mut expected = b"P4\n9 3\n".to_vec();
+        expected.extend([0b0001_1100, 0].repeat(3));
+        assert_eq!(pbm(&scaled, &black_and_white()), expected);
+    }
+
+    #[test]
+    fn uses_luma_to_pick_black() {
+        let palette = Palette::new(vec![
+            [200, 200, 200],
+            [0, 0, 255],
+            [255, 255, 0],
+            [90, 90, 90],
+        ]);
+        let grid = Grid::from_fn(4, 1, |x, _| x as u8);
+        let frame = Frame::from_grid(&grid, |shade| *shade);
+
+        assert_eq!(pbm(&frame, &palette), b"P4\n4 1\n\x50");
+    }
+}
@@ -0,0 +1,35 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Randomly generated puzzle input.
+pub struct GeneratedInput {
+    pub input: String,
+    /// Facts the solver has to rediscover, for generators which know them by construction
+    /// (e.g. scanner poses in day 19).
+    pub ground_truth: Option<String>,
+}
+
+impl From<String> for GeneratedInput {
+    fn from(input: String) -> Self {
+        Self {
+            input,
+            ground_truth: None,
+        }
+    }
+}
+
+/// Source of random inputs which are valid for the day's solver, for stress tests and benchmarks.
+pub trait Generator {
+    /// Size of the real puzzle input. What the size counts - lines, segments, grid side -
+    /// depends on the day; days with a fixed input shape ignore it.
+    const DEFAULT_SIZE: usize;
+
+    fn generate(rng: &mut StdRng, size: usize) -> GeneratedInput;
+}
+
+/// Generates an input from the given seed. The same seed always gives the same input.
+pub fn generate<G: Generator>(seed: u64, size: Option<usize>) -> GeneratedInput {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    G::generate(&mut rng, size.unwrap_or(G::DEFAULT_SIZE))
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+/// Codes in GIF LZW streams are at most 12 bits wide.
+const MAX_CODE: u16 = (1 << 12) - 1;
+
+/// Animated GIF89a encoder for frames of palette indices. The animation loops forever.
+pub(crate) struct GifEncoder {
+    bytes: Vec<u8>,
+    /// Bits per palette index - the colour table holds `1 << depth` colours.
+    depth: u8,
+}
+
+impl GifEncoder {
+    /// `palette` has to have between 1 and 256 colours, it is padded with black up to a power of two.
+    pub(crate) fn new(width: u16, height: u16, palette: &[[u8; 3]]) -> Self {
+        let depth = (1..=8)
+            .find(|depth| palette.len() <= 1 << depth)
+            .unwrap_or(8);
+
+        let mut bytes = b"GIF89a".to_vec();
+
+        // Logical screen descriptor with a global colour table.
+        bytes.extend(width.to_le_bytes());
+        bytes.extend(height.to_le_bytes());
+        bytes.push(0x80 | (depth - 1) << 4 | (depth - 1));
+        bytes.extend([0, 0]);
+
+        for idx in 0..1 << depth {
+            bytes.extend(palette.get(idx).copied().unwrap_or_default());
+        }
+
+        // Netscape application extension - repeat the animation infinitely.
+        bytes.extend([0x21, 0xFF, 0x0B]);
+        bytes.extend(b"NETSCAPE2.0");
+        bytes.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        Self { bytes, depth }
+    }
+
+    /// Adds a frame shown for `delay` hundredths of a second, drawn from the top left corner.
+    pub(crate) fn frame(&mut self, width: u16, height: u16, pixels: &[u8], delay: u16) {
+        // Graphic control extension - frames do not get disposed, they just draw over each other.
+        self.bytes.extend([0x21, 0xF9, 0x04, 0x04]);
+        self.bytes.extend(delay.to_le_bytes());
+        self.bytes.extend([0x00, 0x00]);
+
+        // Image descriptor without a local colour table.
+        self.bytes.push(0x2C);
+        self.bytes.extend([0, 0, 0, 0]);
+        self.bytes.extend(width.to_le_bytes());
+        self.bytes.extend(height.to_le_bytes());
+        self.bytes.push(0);
+
+        let min_code_size = self.depth.max(2);
+        self.bytes.push(min_code_size);
+
+        for block in lzw(pixels, min_code_size).chunks(255) {
+            self.bytes.push(block.len() as u8);
+            self.bytes.extend(block);
+        }
+        self.bytes.push(0);
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        self.bytes.push(0x3B);
+        self.bytes
+    }
+}
+
+/// Packs variable width codes least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += width;
+
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+/// Variable code width LZW as used by GIF. The code width grows as the table fills up,
+/// and the table starts over with a clear code once every 12-bit code is taken.
+struct Lzw {
+    writer: BitWriter,
+    table: HashMap<(u16, u8), u16>,
+    min_code_size: u8,
+    width: u8,
+    last_code: u16,
+}
+
+impl Lzw {
+    fn new(min_code_size: u8) -> Self {
+        let mut lzw = Self {
+            writer: BitWriter::default(),
+            table: HashMap::new(),
+            min_code_size,
+            width: min_code_size + 1,
+            last_code: 0,
+        };
+
+        lzw.clear();
+        lzw
+    }
+
+    fn clear_code(&self) -> u16 {
+        1 << self.min_code_size
+    }
+
+    fn end_of_information(&self) -> u16 {
+        self.clear_code() + 1
+    }
+
+    fn clear(&mut self) {
+        self.writer.write(self.clear_code(), self.width);
+        self.table.clear();
+        self.width = self.min_code_size + 1;
+        self.last_code = self.end_of_information();
+    }
+
+    /// Writes the code and takes the next table entry for it, which may widen the following codes.
+    /// Returns `None` if the table was full and had to be cleared instead.
+    fn write(&mut self, code: u16) -> Option<u16> {
+        self.writer.write(code, self.width);
+        self.last_code += 1;
+
+        if self.last_code == 1 << self.width {
+            self.width += 1;
+        }
+
+        if self.last_code == MAX_CODE {
+            self.clear();
+            None
+        } else {
+            Some(self.last_code)
+        }
+    }
+
+    fn compress(mut self, pixels: &[u8]) -> Vec<u8> {
+        let mut pixels = pixels.iter().copied();
+
+        if let Some(first) = pixels.next() {
+            let mut code = u16::from(first);
+
+            for pixel in pixels {
+                if let Some(&extended) = self.table.get(&(code, pixel)) {
+                    code = extended;
+                    continue;
+                }
+
+                if let Some(entry) = self.write(code) {
+                    self.table.insert((code, pixel), entry);
+                }
+                code = u16::from(pixel);
+            }
+
+            self.write(code);
+        }
+
+        self.writer.write(self.end_of_information(), self.width);
+        self.writer.finish()
+    }
+}
+
+fn lzw(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    Lzw::new(min_code_size).compress(pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What a decoder saw in an LZW stream.
+    struct Decoded {
+        pixels: Vec<u8>,
+        clears: usize,
+        widest_code: u8,
+    }
+
+    /// Straightforward GIF LZW decoder, independent of the encoder's table.
+    fn decode(bytes: &[u8], min_code_size: u8) -> Decoded {
+        let clear_code = 1usize << min_code_size;
+        let end_of_information = clear_code + 1;
+        let initial_table = || -> Vec<Vec<u8>> {
+            (0..clear_code)
+                .map(|pixel| vec![pixel as u8])
+                .chain([vec![], vec![]])
+                .collect()
+        };
+
+        let mut decoded = Decoded {
+            pixels: vec![],
+            clears: 0,
+            widest_code: 0,
+        };
+        let mut table = initial_table();
+        let mut width = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut position = 0;
+
+        loop {
+            let code = (0..usize::from(width)).fold(0, |code, bit| {
+                let bit_position = position + bit;
+                let set = bytes[bit_position / 8] >> (bit_position % 8) & 1;
+                code | usize::from(set) << bit
+            });
+            position += usize::from(width);
+            decoded.widest_code = decoded.widest_code.max(width);
+
+            if code == clear_code {
+                decoded.clears += 1;
+                table = initial_table();
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end_of_information {
+                break;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) if code == table.len() => {
+                    let mut entry = previous.clone();
+                    entry.push(previous[0]);
+                    entry
+                }
+                _ => panic!("code {} is not in the table of {}", code, table.len()),
+            };
+
+            if let Some(mut previous) = previous.take() {
+                if table.len() <= usize::from(MAX_CODE) {
+                    previous.push(entry[0]);
+                    table.push(previous);
+                }
+            }
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+
+            decoded.pixels.extend(&entry);
+            previous = Some(entry);
+        }
+
+        assert_eq!(
+            position.div_ceil(8),
+            bytes.len(),
+            "trailing bytes after the stream"
+        );
+        decoded
+    }
+
+    /// Deterministic pixels with little repetition, so the table fills up quickly.
+    fn noise(len: usize, colours: u32) -> Vec<u8> {
+        let mut state = 0x2545_F491u32;
+
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state % colours) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_short_streams() {
+        for pixels in [
+            vec![],
+            vec![3],
+            vec![1, 1, 1, 1, 1, 1, 1],
+            vec![0, 1, 0, 1, 0, 1, 2, 3],
+        ] {
+            let decoded = decode(&lzw(&pixels, 2), 2);
+
+            assert_eq!(decoded.pixels, pixels);
+            assert_eq!(decoded.clears, 1);
+        }
+    }
+
+    #[test]
+    fn round_trips_past_the_full_code_table() {
+        for (min_code_size, colours) in [(2, 4), (4, 11), (8, 256)] {
+            let pixels = noise(60_000, colours);
+            let decoded = decode(&lzw(&pixels, min_code_size), min_code_size);
+
+            assert_eq!(decoded.pixels, pixels, "min code size {}", min_code_size);
+            assert_eq!(decoded.widest_code, 12);
+            assert!(
+                decoded.clears > 2,
+                "min code size {} cleared the table {} times",
+                min_code_size,
+                decoded.clears
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_long_runs() {
+        let pixels = (0..40_000)
+            .map(|idx| (idx / 700 % 3) as u8)
+            .collect::<Vec<_>>();
+
+        assert_eq!(decode(&lzw(&pixels, 2), 2).pixels, pixels);
+    }
+}
@@ -0,0 +1,403 @@
+use crate::parser::{line_ending, parse_all, Input, Parser};
+use anyhow::Result;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Rectangular 2D grid stored row by row, addressed by `(x, y)` with `(0, 0)` in the top left corner.
+///
+/// A grid may carry a background value. Signed lookups through [`Grid::at`] return it for every
+/// cell outside of the stored area, which lets it model an infinite plane with finite storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    background: Option<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+
+        Self {
+            cells,
+            width,
+            height,
+            background: None,
+        }
+    }
+
+    /// Parses a character map - one row per line, one cell per character.
+    ///
+    /// Every row must be as wide as the first one. `cell` returns `None` for characters which
+    /// are not valid in the map; `expected` describes the valid ones in the error message.
+    pub fn parse(
+        s: &str,
+        expected: &'static str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self> {
+        Ok(parse_all(s, Self::parser(expected, cell))?)
+    }
+
+    /// Character map parser for inputs where the map is one of the sections.
+    /// The map ends with the first empty line.
+    pub fn parser<'a>(
+        expected: &'static str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> impl Parser<'a, Self> {
+        move |mut input: Input<'a>| {
+            let mut cells = vec![];
+            let mut width = None;
+            let mut height = 0;
+
+            loop {
+                let mut line_width = 0;
+                for ch in input
+                    .rest()
+                    .chars()
+                    .take_while(|ch| !matches!(ch, '\n' | '\r'))
+                {
+                    match (width, cell(ch)) {
+                        (Some(width), _) if line_width == width => {
+                            return Err(input.error(format!("end of line after {} cells", width)));
+                        }
+                        (_, Some(value)) => cells.push(value),
+                        (_, None) => return Err(input.error(expected)),
+                    }
+
+                    line_width += 1;
+                    input = input.advance(ch.len_utf8());
+                }
+
+                match width {
+                    None if line_width == 0 => return Err(input.error(expected)),
+                    None => width = Some(line_width),
+                    Some(width) if line_width < width => {
+                        return Err(
+                            input.error(format!("{} (rows are {} cells wide)", expected, width))
+                        );
+                    }
+                    _ => {}
+                }
+                height += 1;
+
+                match line_ending().parse(input) {
+                    Ok((_, next)) if !next.is_empty() && line_ending().parse(next).is_err() => {
+                        input = next;
+                    }
+                    _ => break,
+                }
+            }
+
+            let grid = Self {
+                cells,
+                width: width.unwrap_or_default(),
+                height,
+                background: None,
+            };
+
+            Ok((grid, input))
+        }
+    }
+
+    pub fn with_background(mut self, background: T) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn background(&self) -> Option<&T> {
+        self.background.as_ref()
+    }
+
+    pub fn set_background(&mut self, background: T) {
+        self.background = Some(background);
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Signed lookup which falls back to the background outside of the stored area.
+    pub fn at(&self, x: isize, y: isize) -> Option<&T> {
+        if x < 0 || y < 0 {
+            return self.background.as_ref();
+        }
+
+        self.get(x as usize, y as usize)
+            .or(self.background.as_ref())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row from the top, also for a grid with no columns.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// All positions of the grid in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |idx| (idx % width, idx / width))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(&mut f).collect(),
+            width: self.width,
+            height: self.height,
+            background: self.background.as_ref().map(f),
+        }
+    }
+
+    /// Up, down, left and right neighbours which lie within the grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.bounded_offsets(x, y, &ORTHOGONAL)
+    }
+
+    /// Orthogonal and diagonal neighbours which lie within the grid.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.bounded_offsets(x, y, &ADJACENT)
+    }
+
+    /// Orthogonal neighbours on a torus - leaving one edge enters from the opposite one.
+    pub fn wrapping_neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        ORTHOGONAL
+            .iter()
+            .map(move |delta| wrap((width, height), (x, y), *delta))
+    }
+
+    /// Orthogonal and diagonal neighbours on a torus.
+    pub fn wrapping_neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        ADJACENT
+            .iter()
+            .map(move |delta| wrap((width, height), (x, y), *delta))
+    }
+
+    /// Position reached by moving `(dx, dy)` from `(x, y)` on a torus.
+    pub fn wrapping_offset(&self, (x, y): (usize, usize), delta: (isize, isize)) -> (usize, usize) {
+        wrap((self.width, self.height), (x, y), delta)
+    }
+
+    fn bounded_offsets(
+        &self,
+        x: usize,
+        y: usize,
+        deltas: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        deltas.iter().filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(*dx)?;
+            let ny = y.checked_add_signed(*dy)?;
+
+            (nx < width && ny < height).then_some((nx, ny))
+        })
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+            background: None,
+        }
+    }
+
+    /// Copy of the grid surrounded by `border` cells of background on every side.
+    ///
+    /// # Panics
+    /// When the grid has no background.
+    pub fn grow(&self, border: usize) -> Self {
+        let background = self
+            .background
+            .clone()
+            .expect("only grids with background can grow");
+
+        let mut grown = Self::from_fn(self.width + 2 * border, self.height + 2 * border, |x, y| {
+            self.at(x as isize - border as isize, y as isize - border as isize)
+                .cloned()
+                .unwrap_or_else(|| background.clone())
+        });
+        grown.background = Some(background);
+        grown
+    }
+}
+
+fn wrap(
+    (width, height): (usize, usize),
+    (x, y): (usize, usize),
+    (dx, dy): (isize, isize),
+) -> (usize, usize) {
+    (
+        (x as isize + dx).rem_euclid(width as isize) as usize,
+        (y as isize + dy).rem_euclid(height as isize) as usize,
+    )
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the grid",
+            x,
+            y
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the grid",
+            x,
+            y
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParseError;
+
+    fn digit(ch: char) -> Option<u32> {
+        ch.to_digit(10)
+    }
+
+    fn parse_error(s: &str) -> ParseError {
+        Grid::parse(s, "digit", digit)
+            .expect_err("map should be rejected")
+            .downcast()
+            .expect("should be a parse error")
+    }
+
+    #[test]
+    fn parses_rows_of_cells() {
+        let grid = Grid::parse("123\n456\n", "digit", digit).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+    }
+
+    #[test]
+    fn rejects_rows_shorter_than_the_first() {
+        let error = parse_error("123\n45\n");
+
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert_eq!(error.expected(), ["digit (rows are 3 cells wide)"]);
+    }
+
+    #[test]
+    fn rejects_rows_longer_than_the_first() {
+        let error = parse_error("123\n4567\n");
+
+        assert_eq!((error.line(), error.column()), (2, 4));
+        assert_eq!(error.expected(), ["end of line after 3 cells"]);
+    }
+
+    #[test]
+    fn rejects_empty_rows() {
+        let error = parse_error("\n123\n");
+        assert_eq!((error.line(), error.column()), (1, 1));
+        assert_eq!(error.expected(), ["digit"]);
+
+        // An empty line ends the map, so anything after it is left unparsed.
+        let error = parse_error("123\n\n456\n");
+        assert_eq!((error.line(), error.column()), (3, 1));
+        assert_eq!(error.expected(), ["end of input"]);
+    }
+
+    #[test]
+    fn rejects_invalid_cells() {
+        let error = parse_error("123\n4x6\n");
+
+        assert_eq!((error.line(), error.column()), (2, 2));
+        assert_eq!(error.expected(), ["digit"]);
+    }
+
+    #[test]
+    fn rows_of_a_grid_without_columns_are_empty() {
+        let grid = Grid::new(0, 3, 0);
+
+        assert_eq!(grid.rows().count(), 3);
+        assert!(grid.rows().all(<[_]>::is_empty));
+        assert_eq!(grid.to_string(), "\n\n\n");
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Path which stands for the standard input.
+pub const STDIN: &str = "-";
+
+/// Opens the puzzle input - a file, or the standard input if the path is `-`.
+pub fn open(path: impl AsRef<Path>) -> Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+
+    if path == Path::new(STDIN) {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        let file =
+            File::open(path).with_context(|| format!("failed to open input {}", path.display()))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Reads the whole puzzle input, see [`open`].
+pub fn read(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let mut input = String::new();
+
+    open(path)?
+        .read_to_string(&mut input)
+        .with_context(|| format!("failed to read input {}", path.display()))?;
+
+    Ok(input)
+}
+
+/// How long [`follow`] waits before checking a file for new data again.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Lines of the puzzle input as they are written, like `tail -f`. A file is read from its
+/// beginning and then followed forever, the standard input is followed until it is closed.
+pub fn follow(path: impl AsRef<Path>) -> Result<Follow> {
+    let path = path.as_ref();
+
+    Ok(Follow {
+        reader: open(path)?,
+        wait: path != Path::new(STDIN),
+        line: String::new(),
+    })
+}
+
+/// Iterator returned by [`follow`]. Lines are only yielded once they are complete.
+pub struct Follow {
+    reader: Box<dyn BufRead>,
+    wait: bool,
+    line: String,
+}
+
+impl Iterator for Follow {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.reader.read_line(&mut self.line) {
+                Err(err) => return Some(Err(err)),
+                Ok(_) if self.line.ends_with('\n') => {
+                    let line = std::mem::take(&mut self.line);
+                    return Some(Ok(line.trim_end_matches(['\n', '\r']).to_owned()));
+                }
+                // End of the data written so far - a partially written line stays buffered.
+                Ok(_) if self.wait => thread::sleep(POLL_INTERVAL),
+                Ok(_) if self.line.is_empty() => return None,
+                Ok(_) => return Some(Ok(std::mem::take(&mut self.line))),
+            }
+        }
+    }
+}
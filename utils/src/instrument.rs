@@ -0,0 +1,156 @@
+//! Opt-in solver instrumentation: named counters, maxima and timed spans.
+//!
+//! Solvers call into this module unconditionally. Unless the `instrument` feature of `utils`
+//! is enabled, every call is an empty inline function and compiles away. Metrics are collected
+//! per thread, so parallel runs do not mix each other's numbers; [`take_report`] drains
+//! the metrics of the calling thread.
+#[cfg(feature = "instrument")]
+use std::cell::RefCell;
+#[cfg(feature = "instrument")]
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::time::Duration;
+#[cfg(feature = "instrument")]
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Counter(u64),
+    Maximum(u64),
+    Span { calls: u64, total: Duration },
+}
+
+#[cfg(feature = "instrument")]
+thread_local! {
+    static METRICS: RefCell<BTreeMap<&'static str, Metric>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+#[cfg(feature = "instrument")]
+fn update(name: &'static str, initial: Metric, f: impl FnOnce(&mut Metric)) {
+    METRICS.with(|metrics| f(metrics.borrow_mut().entry(name).or_insert(initial)));
+}
+
+#[inline]
+pub fn count(name: &'static str) {
+    add(name, 1);
+}
+
+#[inline]
+pub fn add(name: &'static str, amount: u64) {
+    #[cfg(feature = "instrument")]
+    update(name, Metric::Counter(0), |metric| {
+        if let Metric::Counter(count) = metric {
+            *count += amount;
+        }
+    });
+
+    #[cfg(not(feature = "instrument"))]
+    let _ = (name, amount);
+}
+
+/// Keeps the highest value seen, e.g. a recursion depth or a collection size.
+#[inline]
+pub fn maximum(name: &'static str, value: u64) {
+    #[cfg(feature = "instrument")]
+    update(name, Metric::Maximum(value), |metric| {
+        if let Metric::Maximum(maximum) = metric {
+            *maximum = (*maximum).max(value);
+        }
+    });
+
+    #[cfg(not(feature = "instrument"))]
+    let _ = (name, value);
+}
+
+/// Measures the time until the span is dropped. Spans with the same name add up.
+#[must_use = "the span is measured until it is dropped"]
+pub struct Span {
+    #[cfg(feature = "instrument")]
+    name: &'static str,
+    #[cfg(feature = "instrument")]
+    start: Instant,
+}
+
+#[inline]
+pub fn span(name: &'static str) -> Span {
+    #[cfg(not(feature = "instrument"))]
+    let _ = name;
+
+    Span {
+        #[cfg(feature = "instrument")]
+        name,
+        #[cfg(feature = "instrument")]
+        start: Instant::now(),
+    }
+}
+
+#[cfg(feature = "instrument")]
+impl Drop for Span {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+
+        update(
+            self.name,
+            Metric::Span {
+                calls: 0,
+                total: Duration::ZERO,
+            },
+            |metric| {
+                if let Metric::Span { calls, total } = metric {
+                    *calls += 1;
+                    *total += elapsed;
+                }
+            },
+        );
+    }
+}
+
+/// Metrics collected on one thread, sorted by name.
+#[derive(Debug, Clone, Default)]
+pub struct Report(Vec<(&'static str, Metric)>);
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn metrics(&self) -> &[(&'static str, Metric)] {
+        &self.0
+    }
+}
+
+/// Returns all metrics collected on this thread so far and starts over.
+pub fn take_report() -> Report {
+    #[cfg(feature = "instrument")]
+    return Report(METRICS.with(|metrics| metrics.take().into_iter().collect()));
+
+    #[cfg(not(feature = "instrument"))]
+    Report::default()
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self.0.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+
+        for (name, metric) in self.0.iter() {
+            match metric {
+                Metric::Counter(count) => {
+                    writeln!(f, "{:<width$}  {}", name, count, width = width)?
+                }
+                Metric::Maximum(maximum) => {
+                    writeln!(f, "{:<width$}  max {}", name, maximum, width = width)?
+                }
+                Metric::Span { calls, total } => writeln!(
+                    f,
+                    "{:<width$}  {} calls, {:?} total",
+                    name,
+                    calls,
+                    total,
+                    width = width
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,12 @@
-pub fn consecutive_pairs<T>(iter: impl Iterator<Item = T> + Clone) -> impl Iterator<Item = (T, T)> {
-    let cloned = iter.clone();
-    iter.zip(cloned.skip(1))
-}
+pub mod frames;
+pub mod generator;
+mod gif;
+pub mod grid;
+pub mod input;
+pub mod instrument;
+pub mod parser;
+pub mod search;
+mod solution;
+pub mod windows;
+
+pub use solution::Solution;
@@ -0,0 +1,600 @@
+//! Small parser combinator toolkit for puzzle inputs.
+//!
+//! Parsers are plain functions from [`Input`] to [`ParseResult`], so days can build their own
+//! from the combinators here or write them by hand where that reads better. Failures carry the
+//! line, column and expected tokens of the problem; the file name is attached by whoever read
+//! the input, see [`in_file`].
+//!
+//! Alternatives backtrack only when the failing branch has not consumed any input - once a line
+//! is recognised (e.g. past the `fold along ` prefix) the error from inside it is reported as is.
+
+use crate::input;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Input<'a> {
+    source: &'a str,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// Moves past the first `len` bytes of the remaining input.
+    pub fn advance(self, len: usize) -> Self {
+        let consumed = &self.rest()[..len];
+        let (line, column) = consumed
+            .chars()
+            .fold((self.line, self.column), |(l, c), ch| {
+                if ch == '\n' {
+                    (l + 1, 1)
+                } else {
+                    (l, c + 1)
+                }
+            });
+
+        Self {
+            offset: self.offset + len,
+            line,
+            column,
+            ..self
+        }
+    }
+
+    /// 1-based line of the current position.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// 1-based column of the current position.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The whole line of the source the current position is on, without the line break.
+    pub fn source_line(&self) -> &'a str {
+        let line_start = self.source[..self.offset]
+            .rfind('\n')
+            .map_or(0, |idx| idx + 1);
+        let line_end = self.source[self.offset..]
+            .find('\n')
+            .map_or(self.source.len(), |idx| self.offset + idx);
+
+        self.source[line_start..line_end].trim_end()
+    }
+
+    /// Error at the current position, describing what was expected here.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            file: None,
+            offset: self.offset,
+            line: self.line,
+            column: self.column,
+            expected: vec![expected.into()],
+            found: describe_token(self.rest()),
+            source_line: self.source_line().to_owned(),
+        }
+    }
+}
+
+fn describe_token(rest: &str) -> String {
+    if rest.is_empty() {
+        "end of input".into()
+    } else if rest.starts_with('\n') || rest.starts_with("\r\n") {
+        "end of line".into()
+    } else {
+        let token = rest
+            .split(char::is_whitespace)
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(16)
+            .collect::<String>();
+
+        if token.is_empty() {
+            format!("{:?}", rest.chars().next().unwrap_or_default())
+        } else {
+            format!("{:?}", token)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    file: Option<PathBuf>,
+    offset: usize,
+    line: usize,
+    column: usize,
+    expected: Vec<String>,
+    found: String,
+    source_line: String,
+}
+
+impl ParseError {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn expected(&self) -> &[String] {
+        &self.expected
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Of two failed alternatives the one which got further is more relevant.
+    /// If both failed at the same spot, either of their tokens would have been fine.
+    fn merge(mut self, other: Self) -> Self {
+        if other.offset > self.offset {
+            return other;
+        }
+
+        if other.offset == self.offset {
+            for expected in other.expected {
+                if !self.expected.contains(&expected) {
+                    self.expected.push(expected);
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}: ", file.display(), self.line, self.column)?,
+            None => write!(f, "line {}, column {}: ", self.line, self.column)?,
+        }
+
+        write!(f, "expected ")?;
+        for (idx, expected) in self.expected.iter().enumerate() {
+            if idx > 0 {
+                let separator = if idx + 1 == self.expected.len() {
+                    " or "
+                } else {
+                    ", "
+                };
+                write!(f, "{}", separator)?;
+            }
+            write!(f, "{}", expected)?;
+        }
+        writeln!(f, ", found {}", self.found)?;
+
+        writeln!(f, "    {}", self.source_line)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Attaches the input file name to a parse error, leaving any other error untouched.
+pub fn in_file(mut error: anyhow::Error, file: impl AsRef<Path>) -> anyhow::Error {
+    if let Some(parse_error) = error.downcast_mut::<ParseError>() {
+        let file = file.as_ref();
+        parse_error.file = Some(if file == Path::new(input::STDIN) {
+            "<stdin>".into()
+        } else {
+            file.to_owned()
+        });
+    }
+
+    error
+}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Runs the parser over the whole source. Only trailing whitespace may be left unparsed.
+pub fn parse_all<'a, T>(source: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    let (value, rest) = parser.parse(Input::new(source))?;
+    let trailing = rest.rest().len() - rest.rest().trim_start().len();
+    let rest = rest.advance(trailing);
+
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(rest.error("end of input"))
+    }
+}
+
+/// Parses every line of the source with the same parser.
+pub fn parse_lines<'a, T>(
+    source: &'a str,
+    parser: impl Parser<'a, T>,
+) -> Result<Vec<T>, ParseError> {
+    parse_all(source, section(parser))
+}
+
+pub fn tag<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| match input.rest().strip_prefix(expected) {
+        Some(_) => Ok((
+            &input.rest()[..expected.len()],
+            input.advance(expected.len()),
+        )),
+        None => Err(input.error(format!("{:?}", expected))),
+    }
+}
+
+/// Single character accepted by `f`.
+pub fn satisfy<'a, T>(expected: &'static str, f: impl Fn(char) -> Option<T>) -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let ch = input.rest().chars().next();
+        match ch.and_then(&f) {
+            Some(value) => Ok((value, input.advance(ch.map_or(0, char::len_utf8)))),
+            None => Err(input.error(expected)),
+        }
+    }
+}
+
+/// Optionally signed decimal integer which has to fit in `T`.
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let sign = usize::from(rest.starts_with(['-', '+']));
+        let digits = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+
+        if digits == 0 {
+            return Err(input.error("integer"));
+        }
+
+        match rest[..sign + digits].parse() {
+            Ok(value) => Ok((value, input.advance(sign + digits))),
+            Err(_) => Err(input.error(format!("integer fitting {}", std::any::type_name::<T>()))),
+        }
+    }
+}
+
+/// One of the given keywords, mapped to its value.
+pub fn one_of<'a, T: Clone, const N: usize>(choices: [(&'static str, T); N]) -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        for (keyword, value) in choices.iter() {
+            if input.rest().starts_with(keyword) {
+                return Ok((value.clone(), input.advance(keyword.len())));
+            }
+        }
+
+        let mut error = input.error(format!("{:?}", choices[0].0));
+        for (keyword, _) in choices.iter().skip(1) {
+            error = error.merge(input.error(format!("{:?}", keyword)));
+        }
+        Err(error)
+    }
+}
+
+/// One or more spaces or tabs.
+pub fn space<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        let len = input.rest().len() - input.rest().trim_start_matches([' ', '\t']).len();
+        if len == 0 {
+            Err(input.error("whitespace"))
+        } else {
+            Ok(((), input.advance(len)))
+        }
+    }
+}
+
+pub fn line_ending<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        if rest.starts_with('\n') {
+            Ok(((), input.advance(1)))
+        } else if rest.starts_with("\r\n") {
+            Ok(((), input.advance(2)))
+        } else {
+            Err(input.error("end of line"))
+        }
+    }
+}
+
+/// Empty line separating sections of the input.
+pub fn blank_line<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        let (_, after) = line_ending().parse(input)?;
+        line_ending()
+            .parse(after)
+            .map_err(|_| input.error("empty line"))
+    }
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: Input<'a>| parser.parse(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// Like [`map`], but `f` can reject the value - the error then points at where it started.
+pub fn try_map<'a, T, U>(
+    parser: impl Parser<'a, T>,
+    expected: &'static str,
+    f: impl Fn(T) -> Option<U>,
+) -> impl Parser<'a, U> {
+    move |input: Input<'a>| {
+        let (value, rest) = parser.parse(input)?;
+        match f(value) {
+            Some(value) => Ok((value, rest)),
+            None => Err(input.error(expected)),
+        }
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: Input<'a>| {
+        let (a, input) = first.parse(input)?;
+        let (b, input) = second.parse(input)?;
+
+        Ok(((a, b), input))
+    }
+}
+
+pub fn preceded<'a, A, B>(
+    prefix: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(prefix, parser), |(_, b)| b)
+}
+
+pub fn terminated<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    suffix: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(parser, suffix), |(a, _)| a)
+}
+
+pub fn delimited<'a, A, B, C>(
+    prefix: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+    suffix: impl Parser<'a, C>,
+) -> impl Parser<'a, B> {
+    preceded(prefix, terminated(parser, suffix))
+}
+
+pub fn separated_pair<'a, A, S, B>(
+    first: impl Parser<'a, A>,
+    separator: impl Parser<'a, S>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    pair(terminated(first, separator), second)
+}
+
+/// Tries `first`, falling back to `second` if it failed without consuming input.
+pub fn or<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: Input<'a>| match first.parse(input) {
+        Ok(result) => Ok(result),
+        Err(error) if error.offset > input.offset => Err(error),
+        Err(error) => second.parse(input).map_err(|other| error.merge(other)),
+    }
+}
+
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: Input<'a>| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(error) if error.offset > input.offset => Err(error),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// One or more repetitions of the parser.
+pub fn many1<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut input) = parser.parse(input)?;
+        let mut values = vec![first];
+
+        loop {
+            match parser.parse(input) {
+                Ok((value, rest)) => {
+                    values.push(value);
+                    input = rest;
+                }
+                Err(error) if error.offset > input.offset => return Err(error),
+                Err(_) => return Ok((values, input)),
+            }
+        }
+    }
+}
+
+/// One or more items with separators between them. The list ends before a separator which is
+/// not followed by an item.
+pub fn separated_list1<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut input) = parser.parse(input)?;
+        let mut values = vec![first];
+
+        loop {
+            let after_separator = match separator.parse(input) {
+                Ok((_, rest)) => rest,
+                Err(error) if error.offset > input.offset => return Err(error),
+                Err(_) => return Ok((values, input)),
+            };
+
+            match parser.parse(after_separator) {
+                Ok((value, rest)) => {
+                    values.push(value);
+                    input = rest;
+                }
+                Err(error) if error.offset > after_separator.offset => return Err(error),
+                Err(_) => return Ok((values, input)),
+            }
+        }
+    }
+}
+
+/// One or more lines parsed by `parser`, up to the next empty line or the end of input.
+///
+/// Every line in the section has to parse, so a malformed line is reported where it is
+/// instead of quietly ending the list.
+pub fn section<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut input) = parser.parse(input)?;
+        let mut values = vec![first];
+
+        loop {
+            let next_line = match line_ending().parse(input) {
+                Ok((_, next_line)) => next_line,
+                Err(_) => return Ok((values, input)),
+            };
+
+            if next_line.is_empty() || line_ending().parse(next_line).is_ok() {
+                return Ok((values, input));
+            }
+
+            let (value, rest) = parser.parse(next_line)?;
+            values.push(value);
+            input = rest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(error: &ParseError) -> (usize, usize) {
+        (error.line(), error.column())
+    }
+
+    #[test]
+    fn tracks_lines_and_columns() {
+        let input = Input::new("ab\ncd").advance(4);
+
+        assert_eq!((input.line(), input.column()), (2, 2));
+        assert_eq!(input.source_line(), "cd");
+    }
+
+    #[test]
+    fn tag_reports_where_it_failed() {
+        let error = parse_all("fold x", preceded(tag("fold "), tag("along"))).unwrap_err();
+
+        assert_eq!(position(&error), (1, 6));
+        assert_eq!(error.expected(), ["\"along\""]);
+    }
+
+    #[test]
+    fn integer_has_to_fit_the_type() {
+        assert_eq!(parse_all("-42", integer::<i8>()), Ok(-42));
+
+        let error = parse_all("300", integer::<u8>()).unwrap_err();
+        assert_eq!(position(&error), (1, 1));
+        assert_eq!(error.expected(), ["integer fitting u8"]);
+
+        let error = parse_all("x", integer::<u8>()).unwrap_err();
+        assert_eq!(error.expected(), ["integer"]);
+    }
+
+    #[test]
+    fn alternatives_failing_at_the_same_spot_are_merged() {
+        let error = parse_all("c", or(tag("a"), tag("b"))).unwrap_err();
+
+        assert_eq!(position(&error), (1, 1));
+        assert_eq!(error.expected(), ["\"a\"", "\"b\""]);
+        assert!(error
+            .to_string()
+            .contains("expected \"a\" or \"b\", found \"c\""));
+    }
+
+    #[test]
+    fn alternatives_do_not_backtrack_after_consuming_input() {
+        let parser = or(preceded(tag("x="), integer::<u32>()), integer());
+        let error = parse_all("x=y", parser).unwrap_err();
+
+        assert_eq!(position(&error), (1, 3));
+        assert_eq!(error.expected(), ["integer"]);
+    }
+
+    #[test]
+    fn try_map_points_at_the_start_of_the_value() {
+        let even = try_map(integer::<u32>(), "even number", |n| {
+            (n % 2 == 0).then_some(n)
+        });
+        let error = parse_all("x 7", preceded(tag("x "), even)).unwrap_err();
+
+        assert_eq!(position(&error), (1, 3));
+        assert_eq!(error.expected(), ["even number"]);
+    }
+
+    #[test]
+    fn parse_all_rejects_trailing_input() {
+        let error = parse_all("12 34", integer::<u32>()).unwrap_err();
+
+        assert_eq!(position(&error), (1, 4));
+        assert_eq!(error.expected(), ["end of input"]);
+        assert_eq!(parse_all("12\n\n", integer::<u32>()), Ok(12));
+    }
+
+    #[test]
+    fn malformed_lines_are_reported_where_they_are() {
+        assert_eq!(
+            parse_lines("1\n2\n3\n", integer::<u32>()),
+            Ok(vec![1, 2, 3])
+        );
+
+        let error = parse_lines("1\n2\nthree\n", integer::<u32>()).unwrap_err();
+        assert_eq!(position(&error), (3, 1));
+        assert_eq!(error.expected(), ["integer"]);
+    }
+
+    #[test]
+    fn separated_list_stops_before_a_dangling_separator() {
+        let parser = terminated(separated_list1(integer::<u32>(), tag(",")), tag(","));
+
+        assert_eq!(parse_all("1,2,", parser), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn errors_name_the_file() {
+        let error = parse_all("x", integer::<u32>()).unwrap_err();
+        let error = in_file(error.into(), "day1/input");
+
+        assert!(error
+            .to_string()
+            .starts_with("day1/input:1:1: expected integer"));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::instrument;
+use fxhash::FxHashMap;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Shortest path problem over an implicit graph - states are discovered through `successors`.
+pub trait SearchProblem {
+    type State: Clone + Eq + Hash;
+    /// Path cost. `Default` has to be the zero cost.
+    type Cost: Copy + Ord + Default + Add<Output = Self::Cost>;
+
+    fn start(&self) -> Self::State;
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// States reachable in one move, each with the cost of that move. Costs must not be negative.
+    fn successors(&self, state: &Self::State) -> impl Iterator<Item = (Self::State, Self::Cost)>;
+
+    /// Lower bound of the cost from the state to the nearest goal, used by [`astar`].
+    /// It must never overestimate, otherwise the found path may not be the cheapest one.
+    /// Without a heuristic A* degrades to Dijkstra.
+    fn heuristic(&self, _state: &Self::State) -> Self::Cost {
+        Self::Cost::default()
+    }
+}
+
+/// Cheapest path found by the search, with both the start and the goal state included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+/// Cheapest known cost of every discovered state, together with the state it was reached from.
+type Visited<S, C> = FxHashMap<S, (C, Option<S>)>;
+
+/// Heap entry ordered by priority only, cheapest first - `BinaryHeap` is a max-heap.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+fn search<P: SearchProblem>(
+    problem: &P,
+    heuristic: impl Fn(&P::State) -> P::Cost,
+) -> Option<Path<P::State, P::Cost>> {
+    let start = problem.start();
+    let zero = P::Cost::default();
+
+    let mut best: Visited<P::State, P::Cost> = FxHashMap::default();
+    best.insert(start.clone(), (zero, None));
+
+    let mut heap = BinaryHeap::new();
+    heap.push(Entry {
+        priority: heuristic(&start),
+        cost: zero,
+        state: start,
+    });
+
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        instrument::count("search.heap_pops");
+
+        // A cheaper way to this state has been found after this entry was pushed.
+        if best
+            .get(&state)
+            .is_some_and(|(best_cost, _)| *best_cost < cost)
+        {
+            instrument::count("search.stale_pops");
+            continue;
+        }
+
+        if problem.is_goal(&state) {
+            instrument::maximum("search.visited_states", best.len() as u64);
+            return Some(Path {
+                cost,
+                states: reconstruct(&best, state),
+            });
+        }
+
+        for (next, move_cost) in problem.successors(&state) {
+            let next_cost = cost + move_cost;
+
+            if best
+                .get(&next)
+                .is_none_or(|(best_cost, _)| next_cost < *best_cost)
+            {
+                best.insert(next.clone(), (next_cost, Some(state.clone())));
+                heap.push(Entry {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    instrument::maximum("search.visited_states", best.len() as u64);
+    None
+}
+
+fn reconstruct<S: Clone + Eq + Hash, C>(best: &Visited<S, C>, goal: S) -> Vec<S> {
+    let mut states = vec![goal];
+
+    while let Some((_, Some(previous))) = best.get(&states[states.len() - 1]) {
+        states.push(previous.clone());
+    }
+
+    states.reverse();
+    states
+}
+
+/// Cheapest path from the start to any goal state, ignoring the heuristic.
+pub fn dijkstra<P: SearchProblem>(problem: &P) -> Option<Path<P::State, P::Cost>> {
+    search(problem, |_| P::Cost::default())
+}
+
+/// Cheapest path from the start to any goal state, exploring states with the lowest
+/// `cost + heuristic` first.
+pub fn astar<P: SearchProblem>(problem: &P) -> Option<Path<P::State, P::Cost>> {
+    search(problem, |state| problem.heuristic(state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Directed graph with weighted edges between numbered nodes.
+    struct Graph {
+        edges: Vec<(u8, u8, u32)>,
+        goal: u8,
+        expanded: RefCell<Vec<u8>>,
+    }
+
+    impl Graph {
+        fn new(edges: &[(u8, u8, u32)], goal: u8) -> Self {
+            Self {
+                edges: edges.to_vec(),
+                goal,
+                expanded: RefCell::default(),
+            }
+        }
+    }
+
+    impl SearchProblem for Graph {
+        type State = u8;
+        type Cost = u32;
+
+        fn start(&self) -> u8 {
+            0
+        }
+
+        fn is_goal(&self, state: &u8) -> bool {
+            *state == self.goal
+        }
+
+        fn successors(&self, state: &u8) -> impl Iterator<Item = (u8, u32)> {
+            self.expanded.borrow_mut().push(*state);
+            let state = *state;
+
+            self.edges
+                .iter()
+                .filter(move |(from, _, _)| *from == state)
+                .map(|(_, to, cost)| (*to, *cost))
+        }
+    }
+
+    /// Grid of move costs, walked in four directions from the top left to the bottom right
+    /// corner, like the day 15 cave.
+    struct Cave(Vec<Vec<u32>>);
+
+    impl SearchProblem for Cave {
+        type State = (usize, usize);
+        type Cost = u32;
+
+        fn start(&self) -> Self::State {
+            (0, 0)
+        }
+
+        fn is_goal(&self, state: &Self::State) -> bool {
+            *state == (self.0[0].len() - 1, self.0.len() - 1)
+        }
+
+        fn successors(&self, &(x, y): &Self::State) -> impl Iterator<Item = (Self::State, u32)> {
+            [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .filter_map(move |(dx, dy)| {
+                    let x = x.checked_add_signed(dx)?;
+                    let y = y.checked_add_signed(dy)?;
+                    let cost = *self.0.get(y)?.get(x)?;
+
+                    Some(((x, y), cost))
+                })
+        }
+
+        fn heuristic(&self, &(x, y): &Self::State) -> u32 {
+            let (goal_x, goal_y) = (self.0[0].len() - 1, self.0.len() - 1);
+
+            ((goal_x - x) + (goal_y - y)) as u32
+        }
+    }
+
+    #[test]
+    fn skips_entries_made_stale_by_a_cheaper_path() {
+        // 1 is first reached directly for 5 and then through 2 for 2.
+        let graph = Graph::new(&[(0, 1, 5), (0, 2, 1), (2, 1, 1), (1, 3, 10)], 3);
+        let path = dijkstra(&graph).unwrap();
+
+        assert_eq!(path.cost, 12);
+        assert_eq!(*graph.expanded.borrow(), [0, 2, 1]);
+    }
+
+    #[test]
+    fn path_includes_the_start_and_the_goal() {
+        let graph = Graph::new(&[(0, 1, 5), (0, 2, 1), (2, 1, 1), (1, 3, 10)], 3);
+        let path = dijkstra(&graph).unwrap();
+
+        assert_eq!(path.states, [0, 2, 1, 3]);
+
+        let graph = Graph::new(&[(0, 1, 1)], 0);
+        let path = dijkstra(&graph).unwrap();
+
+        assert_eq!(
+            path,
+            Path {
+                cost: 0,
+                states: vec![0]
+            }
+        );
+    }
+
+    #[test]
+    fn astar_and_dijkstra_find_equally_cheap_paths() {
+        let cave = Cave(
+            [
+                "1163751742",
+                "1381373672",
+                "2136511328",
+                "3694931569",
+                "7463417111",
+                "1319128137",
+                "1359912421",
+                "3125421639",
+                "1293138521",
+                "2311944581",
+            ]
+            .iter()
+            .map(|row| row.chars().map(|c| c.to_digit(10).unwrap()).collect())
+            .collect(),
+        );
+
+        let shortest = dijkstra(&cave).unwrap();
+        let guided = astar(&cave).unwrap();
+
+        assert_eq!(shortest.cost, 40);
+        assert_eq!(guided.cost, shortest.cost);
+        assert_eq!(guided.states.first(), Some(&(0, 0)));
+        assert_eq!(guided.states.last(), Some(&(9, 9)));
+    }
+
+    #[test]
+    fn unreachable_goal_has_no_path() {
+        let graph = Graph::new(&[(0, 1, 1), (1, 0, 1), (2, 3, 1)], 3);
+
+        assert_eq!(dijkstra(&graph), None);
+        assert_eq!(astar(&graph), None);
+        assert_eq!(*graph.expanded.borrow(), [0, 1, 0, 1]);
+    }
+}
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use std::fmt::Display;
+use std::io::Read;
+
+/// Common shape of every day's puzzle: the raw input is parsed once and both parts are
+/// answered from the parsed representation.
+pub trait Solution {
+    /// Parsed puzzle input, shared by both parts.
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    /// Version of the solver. Bump it whenever a change can alter the answers, so answers
+    /// cached by the runner for the old version are not reused.
+    const VERSION: u32 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    /// Parses input from any reader, e.g. a file or the standard input (see [`crate::input::open`]).
+    fn parse_reader(mut reader: impl Read) -> Result<Self::Input> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        Self::parse(&input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2>;
+}
@@ -0,0 +1,222 @@
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+/// Streaming window adapters. Unlike `slice::windows` they work on any iterator, including
+/// unbounded ones like lines of a reader, and only ever keep the current window in memory.
+pub trait WindowExt: Iterator + Sized {
+    /// Overlapping windows of `N` consecutive items, e.g. `[1, 2, 3], [2, 3, 4], ...`.
+    /// Yields nothing if the iterator has fewer than `N` items. Panics if `N` is 0.
+    fn windows<const N: usize>(self) -> Windows<Self, N>
+    where
+        Self::Item: Clone,
+    {
+        assert!(N > 0, "window size has to be at least 1");
+
+        Windows {
+            iter: self,
+            window: VecDeque::with_capacity(N),
+        }
+    }
+
+    /// Sums of overlapping windows of `size` consecutive items. Every sum is computed from the
+    /// previous one in constant time. Panics if `size` is 0.
+    fn rolling_sums(self, size: usize) -> RollingSums<Self>
+    where
+        Self::Item: Copy + Default + Add<Output = Self::Item> + Sub<Output = Self::Item>,
+    {
+        assert!(size > 0, "window size has to be at least 1");
+
+        RollingSums {
+            iter: self,
+            window: VecDeque::with_capacity(size),
+            size,
+            sum: Default::default(),
+        }
+    }
+
+    /// Pairs every item with the one `lag` positions before it, as `(earlier, current)`.
+    /// The first `lag` items only appear as the earlier half of a pair.
+    fn lagged(self, lag: usize) -> Lagged<Self>
+    where
+        Self::Item: Clone,
+    {
+        Lagged {
+            iter: self,
+            earlier: VecDeque::with_capacity(lag + 1),
+            lag,
+        }
+    }
+}
+
+impl<I: Iterator> WindowExt for I {}
+
+/// Iterator returned by [`WindowExt::windows`].
+pub struct Windows<I: Iterator, const N: usize> {
+    iter: I,
+    window: VecDeque<I::Item>,
+}
+
+impl<I: Iterator, const N: usize> Iterator for Windows<I, N>
+where
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == N {
+            self.window.pop_front();
+        }
+
+        while self.window.len() < N {
+            self.window.push_back(self.iter.next()?);
+        }
+
+        Some(std::array::from_fn(|idx| self.window[idx].clone()))
+    }
+}
+
+/// Iterator returned by [`WindowExt::rolling_sums`].
+pub struct RollingSums<I: Iterator> {
+    iter: I,
+    window: VecDeque<I::Item>,
+    size: usize,
+    sum: I::Item,
+}
+
+impl<I: Iterator> Iterator for RollingSums<I>
+where
+    I::Item: Copy + Add<Output = I::Item> + Sub<Output = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == self.size {
+            if let Some(oldest) = self.window.pop_front() {
+                self.sum = self.sum - oldest;
+            }
+        }
+
+        while self.window.len() < self.size {
+            let item = self.iter.next()?;
+            self.sum = self.sum + item;
+            self.window.push_back(item);
+        }
+
+        Some(self.sum)
+    }
+}
+
+/// Iterator returned by [`WindowExt::lagged`].
+pub struct Lagged<I: Iterator> {
+    iter: I,
+    earlier: VecDeque<I::Item>,
+    lag: usize,
+}
+
+impl<I: Iterator> Iterator for Lagged<I>
+where
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.earlier.len() < self.lag {
+            self.earlier.push_back(self.iter.next()?);
+        }
+
+        let current = self.iter.next()?;
+        self.earlier.push_back(current.clone());
+
+        Some((self.earlier.pop_front()?, current))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_slide_by_one_item() {
+        let windows: Vec<_> = (1..=5).windows::<3>().collect();
+
+        assert_eq!(windows, [[1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+    }
+
+    #[test]
+    fn windows_of_fewer_items_than_n_are_empty() {
+        assert_eq!((1..=2).windows::<3>().next(), None);
+        assert_eq!((1..=3).windows::<3>().count(), 1);
+        assert_eq!(std::iter::empty::<u8>().windows::<1>().next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size has to be at least 1")]
+    fn windows_of_size_zero_panic() {
+        let _ = (1..=3).windows::<0>();
+    }
+
+    #[test]
+    fn rolling_sums_match_summed_windows() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let expected: Vec<i32> = depths
+            .iter()
+            .copied()
+            .windows::<3>()
+            .map(|window| window.iter().sum())
+            .collect();
+
+        assert_eq!(
+            depths.iter().copied().rolling_sums(3).collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(
+            depths.iter().copied().rolling_sums(1).collect::<Vec<_>>(),
+            depths
+        );
+    }
+
+    #[test]
+    fn rolling_sums_of_fewer_items_than_size_are_empty() {
+        assert_eq!((1..=2).rolling_sums(3).next(), None);
+        assert_eq!((1..=3).rolling_sums(3).collect::<Vec<_>>(), [6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size has to be at least 1")]
+    fn rolling_sums_of_size_zero_panic() {
+        let _ = (1..=3).rolling_sums(0);
+    }
+
+    #[test]
+    fn lagged_pairs_items_lag_positions_apart() {
+        let pairs: Vec<_> = (1..=5).lagged(2).collect();
+
+        assert_eq!(pairs, [(1, 3), (2, 4), (3, 5)]);
+        assert_eq!((1..=2).lagged(2).next(), None);
+    }
+
+    #[test]
+    fn lag_zero_pairs_every_item_with_itself() {
+        let pairs: Vec<_> = (1..=3).lagged(0).collect();
+
+        assert_eq!(pairs, [(1, 1), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn exhausted_adapters_stay_exhausted() {
+        let mut windows = (1..=3).windows::<2>();
+        assert_eq!(windows.by_ref().count(), 2);
+        assert_eq!(windows.next(), None);
+        assert_eq!(windows.next(), None);
+
+        let mut sums = (1..=3).rolling_sums(2);
+        assert_eq!(sums.by_ref().collect::<Vec<_>>(), [3, 5]);
+        assert_eq!(sums.next(), None);
+        assert_eq!(sums.next(), None);
+
+        let mut pairs = (1..=3).lagged(1);
+        assert_eq!(pairs.by_ref().count(), 2);
+        assert_eq!(pairs.next(), None);
+        assert_eq!(pairs.next(), None);
+    }
+}